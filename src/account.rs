@@ -0,0 +1,79 @@
+// src/account.rs
+// Общие сведения об аккаунтах и адреса стандартных программ
+use anyhow::{Context as AnyhowContext, Result};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::rpc;
+use crate::transaction;
+
+pub const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+pub const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const STAKE_PROGRAM: &str = "Stake11111111111111111111111111111111111111";
+
+// Аккаунт из ответа getAccountInfo / getProgramAccounts
+#[derive(Debug, Deserialize)]
+pub struct AccountInfo {
+    pub lamports: u64,
+    pub owner: String,
+    pub executable: bool,
+    // В кодировке jsonParsed здесь разобранные данные программы
+    pub data: Value,
+}
+
+// Аккаунт вместе с его адресом
+#[derive(Debug, Deserialize)]
+pub struct KeyedAccount {
+    pub pubkey: String,
+    pub account: AccountInfo,
+}
+
+// Аккаунт с разобранными данными; None, если аккаунт не существует
+pub async fn get_account_info(rpc_url: &str, address: &str) -> Result<Option<AccountInfo>> {
+    let info: rpc::WithContext<Option<AccountInfo>> = rpc::call_with_context(
+        rpc_url,
+        "getAccountInfo",
+        json!([address, { "encoding": "jsonParsed" }]),
+    )
+    .await
+    .with_context(|| format!("Failed to fetch account info for {}", address))?;
+
+    Ok(info.value)
+}
+
+// Поле info из разобранных данных аккаунта
+pub fn parsed_info(account: &AccountInfo) -> Option<&Value> {
+    account.data.pointer("/parsed/info")
+}
+
+// Данные аккаунта, полученного в кодировке base64
+pub fn raw_data(account: &AccountInfo) -> Option<Vec<u8>> {
+    let encoded = account.data.get(0)?.as_str()?;
+    base64::engine::general_purpose::STANDARD.decode(encoded).ok()
+}
+
+// Сырые данные аккаунта по адресу
+pub async fn get_account_data(rpc_url: &str, address: &str) -> Result<Vec<u8>> {
+    let info: rpc::WithContext<Option<AccountInfo>> = rpc::call_with_context(
+        rpc_url,
+        "getAccountInfo",
+        json!([address, { "encoding": "base64" }]),
+    )
+    .await
+    .with_context(|| format!("Failed to fetch account info for {}", address))?;
+
+    let account = info.value.with_context(|| format!("Account {} not found", address))?;
+    raw_data(&account).with_context(|| format!("Account {} has invalid base64 data", address))
+}
+
+// Целое little-endian по смещению в данных аккаунта
+pub fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+// Адрес по смещению в данных аккаунта
+pub fn read_pubkey(data: &[u8], offset: usize) -> Option<String> {
+    Some(transaction::encode_pubkey(data.get(offset..offset + 32)?.try_into().ok()?))
+}
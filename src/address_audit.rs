@@ -0,0 +1,58 @@
+// src/address_audit.rs
+// Проверка, что адреса из конфигурации действительно являются обычными кошельками
+use anyhow::Result;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use futures::future::join_all;
+
+use crate::account;
+use crate::transaction::{self, Pubkey};
+
+// Адрес обычного кошелька - точка на кривой ed25519; PDA лежат вне кривой
+pub fn is_on_curve(pubkey: &Pubkey) -> bool {
+    CompressedEdwardsY(*pubkey).decompress().is_some()
+}
+
+// Проверка всех кошельков: формат, принадлежность кривой и владелец аккаунта
+pub async fn run(rpc_url: &str, wallets: &[String]) -> Result<()> {
+    let accounts = join_all(wallets.iter().map(|wallet| account::get_account_info(rpc_url, wallet))).await;
+
+    println!("Address audit for {} wallets:", wallets.len());
+    let mut warnings = 0;
+    for (wallet, account) in wallets.iter().zip(accounts) {
+        let pubkey = match transaction::decode_pubkey(wallet) {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                warnings += 1;
+                println!("[WARN] {}: invalid address: {:#}", wallet, e);
+                continue;
+            }
+        };
+
+        let mut problems = Vec::new();
+        if !is_on_curve(&pubkey) {
+            problems.push("off-curve address (PDA), no private key can sign for it".to_string());
+        }
+        match account {
+            Ok(Some(info)) if info.owner != account::SYSTEM_PROGRAM => {
+                problems.push(format!("account is owned by program {}", info.owner));
+            }
+            Ok(_) => {}
+            Err(e) => problems.push(format!("owner check failed: {:#}", e)),
+        }
+
+        if problems.is_empty() {
+            println!("[OK] {}: on-curve, system-owned or empty", wallet);
+        } else {
+            warnings += problems.len();
+            for problem in problems {
+                println!("[WARN] {}: {}", wallet, problem);
+            }
+        }
+    }
+
+    if warnings > 0 {
+        anyhow::bail!("Address audit found {} warnings", warnings);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// src/backfill.rs
+// Выгрузка всей истории подписей адреса в JSONL файл с контрольной точкой и продолжением после сбоя
+use anyhow::{Context as AnyhowContext, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::format;
+use crate::rpc;
+use crate::run_stats;
+use crate::tx::SignatureInfo;
+
+// Максимальный размер страницы getSignaturesForAddress
+const PAGE: usize = 1000;
+
+// Сколько раз повторять запрос страницы (в том числе после HTTP 429) до остановки
+const MAX_ATTEMPTS: u32 = 6;
+
+// Прогресс выводится раз в столько страниц
+const PROGRESS_EVERY: u64 = 10;
+
+// Контрольная точка: с какой подписи продолжать и сколько байт файла уже подтверждено
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    before: Option<String>,
+    signatures: u64,
+    bytes: u64,
+    complete: bool,
+}
+
+impl Checkpoint {
+    fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Checkpoint::default());
+        }
+        let text = fs::read_to_string(path).with_context(|| format!("Failed to read checkpoint {:?}", path))?;
+        serde_json::from_str(&text).with_context(|| format!("Invalid checkpoint {:?}", path))
+    }
+
+    // Замена целиком, чтобы сбой во время записи не оставил испорченный файл
+    fn save(&self, path: &Path) -> Result<()> {
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, serde_json::to_string(self)?).with_context(|| format!("Failed to write {:?}", temp))?;
+        fs::rename(&temp, path).with_context(|| format!("Failed to replace checkpoint {:?}", path))
+    }
+}
+
+// Выгрузка подписей address от новых к старым в dir/<address>.jsonl; delay - пауза между страницами
+pub async fn run(rpc_url: &str, address: &str, dir: &Path, delay: Duration) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    let output: PathBuf = dir.join(format!("{}.jsonl", address));
+    let checkpoint_path = dir.join(format!("{}.checkpoint.json", address));
+
+    let mut checkpoint = Checkpoint::load(&checkpoint_path)?;
+    if checkpoint.complete {
+        println!("{}: backfill already complete, {} signatures in {:?}", address, checkpoint.signatures, output);
+        return Ok(());
+    }
+    if checkpoint.signatures > 0 {
+        println!("{}: resuming after {} signatures", address, checkpoint.signatures);
+    }
+
+    // Строки, записанные после последней контрольной точки, отбрасываются и запрашиваются заново
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&output)
+        .with_context(|| format!("Failed to open {:?}", output))?;
+    file.set_len(checkpoint.bytes).with_context(|| format!("Failed to truncate {:?}", output))?;
+
+    let mut pages = 0;
+    loop {
+        let page = fetch_page(rpc_url, address, checkpoint.before.as_deref()).await?;
+
+        let mut chunk = String::new();
+        for info in &page {
+            chunk.push_str(&serde_json::to_string(info)?);
+            chunk.push('\n');
+        }
+        file.write_all(chunk.as_bytes()).with_context(|| format!("Failed to write {:?}", output))?;
+        file.sync_data().with_context(|| format!("Failed to sync {:?}", output))?;
+
+        checkpoint.signatures += page.len() as u64;
+        checkpoint.bytes += chunk.len() as u64;
+        checkpoint.complete = page.len() < PAGE;
+        if let Some(last) = page.last() {
+            checkpoint.before = Some(last.signature.clone());
+        }
+        checkpoint.save(&checkpoint_path)?;
+
+        pages += 1;
+        if checkpoint.complete || pages % PROGRESS_EVERY == 0 {
+            let oldest = page.last().and_then(|info| info.block_time).map_or_else(|| "unknown".to_string(), format::time);
+            println!("{}: {} signatures, oldest {}", address, checkpoint.signatures, oldest);
+        }
+        if checkpoint.complete {
+            break;
+        }
+        tokio::time::sleep(delay).await;
+    }
+
+    println!("{}: backfill complete, {} signatures in {:?}", address, checkpoint.signatures, output);
+    Ok(())
+}
+
+// Страница подписей с повторами и нарастающей паузой; после остановки запуск продолжится с контрольной точки
+async fn fetch_page(rpc_url: &str, address: &str, before: Option<&str>) -> Result<Vec<SignatureInfo>> {
+    let mut options = json!({ "limit": PAGE });
+    if let Some(before) = before {
+        options["before"] = json!(before);
+    }
+
+    let mut attempt = 1;
+    loop {
+        match rpc::call(rpc_url, "getSignaturesForAddress", json!([address, options])).await {
+            Ok(page) => return Ok(page),
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                let wait = Duration::from_secs(1 << attempt);
+                println!("Signature page failed (attempt {}), retrying in {:?}: {:#}", attempt, wait, e);
+                run_stats::retry();
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to fetch signatures for {}; run backfill again to resume", address)
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// src/bench.rs
+// Замеры внутренних этапов обработки на синтетических данных, без обращения к сети
+use anyhow::Result;
+use rand::Rng;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::format;
+use crate::rpc;
+use crate::token;
+use crate::transaction;
+
+// Токен-аккаунтов на кошелек в синтетических данных
+const TOKEN_ACCOUNTS_PER_WALLET: usize = 2;
+
+// Синтетический кошелек: адрес и ответы RPC узла
+struct Sample {
+    address: String,
+    balance: Value,
+    token_accounts: Vec<Value>,
+}
+
+// Прогон всех этапов для каждого размера набора
+pub fn run(sizes: &[usize]) -> Result<()> {
+    for &size in sizes {
+        println!("Dataset: {} wallets", size);
+        let samples = generate(size);
+
+        let (balances, parse) = measure(|| {
+            samples
+                .iter()
+                .map(|sample| rpc::parse_with_context::<u64>("getBalance", sample.balance.clone()))
+                .collect::<Result<Vec<_>>>()
+        });
+        let balances = balances?;
+        report("parse getBalance", parse, size);
+
+        let (accounts, parse_tokens) = measure(|| {
+            samples
+                .iter()
+                .flat_map(|sample| sample.token_accounts.iter().map(move |info| (sample, info)))
+                .map(|(sample, info)| token::parse_token_account(sample.address.clone(), crate::account::TOKEN_PROGRAM, 2_039_280, info))
+                .collect::<Vec<_>>()
+        });
+        report("parse token accounts", parse_tokens, accounts.len());
+
+        let (_, aggregate) = measure(|| {
+            let latest = balances.iter().filter_map(|balance| balance.slot).max().unwrap_or(0);
+            let total: u64 = balances.iter().map(|balance| balance.value).sum();
+            let max_lag = balances.iter().filter_map(|balance| balance.slot).map(|slot| latest - slot).max();
+            let tokens: u64 = accounts.iter().map(|account| account.amount).sum();
+            (total, max_lag, tokens)
+        });
+        report("aggregate", aggregate, size);
+
+        let (output, render) = measure(|| {
+            let mut output = String::new();
+            for (sample, balance) in samples.iter().zip(&balances) {
+                let _ = writeln!(output, "{}: {} SOL", sample.address, format::sol(balance.value));
+            }
+            output
+        });
+        report("render output", render, size);
+        println!("  output size: {} bytes", output.len());
+    }
+
+    Ok(())
+}
+
+// Время выполнения f
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let started = Instant::now();
+    let result = black_box(f());
+    (result, started.elapsed())
+}
+
+fn report(stage: &str, elapsed: Duration, items: usize) {
+    let per_second = items as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    println!("  {:<22} {:>10.1} ms  {:>12.0} items/s", stage, elapsed.as_secs_f64() * 1000.0, per_second);
+}
+
+// Ответы в том виде, в каком их отдает RPC узел
+fn generate(size: usize) -> Vec<Sample> {
+    let mut rng = rand::thread_rng();
+    let mut pubkey = || transaction::encode_pubkey(&rng.gen());
+
+    (0..size)
+        .map(|i| {
+            let address = pubkey();
+            let mint = pubkey();
+            let slot = 300_000_000 + (i % 50) as u64;
+            let token_info = json!({
+                "mint": mint,
+                "owner": address,
+                "state": "initialized",
+                "tokenAmount": { "amount": (i * 1_000).to_string(), "decimals": 6 },
+            });
+
+            Sample {
+                balance: json!({ "context": { "slot": slot }, "value": i as u64 * 1_000_000 }),
+                token_accounts: vec![token_info; TOKEN_ACCOUNTS_PER_WALLET],
+                address,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,107 @@
+// src/bridges.rs
+// Токены, заблокированные в кастодиальных аккаунтах мостов, и оставшееся в обращении на Solana предложение
+use anyhow::{Context as AnyhowContext, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::account;
+use crate::format;
+use crate::rpc;
+use crate::transaction;
+
+// Вычисление адреса кастодиального аккаунта моста для mint
+type CustodyAddress = fn(&str, &str) -> Result<String>;
+
+// Адаптер моста: программа и способ найти аккаунт, где лежат заблокированные токены
+#[derive(Debug)]
+pub struct Adapter {
+    pub name: &'static str,
+    program: &'static str,
+    custody: CustodyAddress,
+}
+
+pub const ADAPTERS: &[Adapter] = &[
+    // Wormhole Token Bridge: токены Solana, отправленные в другие сети, лежат в PDA с seeds [mint]
+    Adapter {
+        name: "wormhole",
+        program: "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb",
+        custody: mint_seeded_custody,
+    },
+];
+
+fn mint_seeded_custody(program: &str, mint: &str) -> Result<String> {
+    let mint = transaction::decode_pubkey(mint)?;
+    let (address, _) = transaction::find_program_address(&[&mint], &transaction::decode_pubkey(program)?)?;
+    Ok(transaction::encode_pubkey(&address))
+}
+
+// Токен из секции bridged_tokens
+#[derive(Debug, Deserialize)]
+pub struct BridgedToken {
+    pub mint: String,
+    pub symbol: Option<String>,
+}
+
+// Предложение токена в минимальных единицах
+#[derive(Debug, Deserialize)]
+struct TokenSupply {
+    amount: String,
+    decimals: u32,
+}
+
+async fn get_supply(rpc_url: &str, mint: &str) -> Result<TokenSupply> {
+    let supply: rpc::WithContext<TokenSupply> = rpc::call_with_context(rpc_url, "getTokenSupply", json!([mint]))
+        .await
+        .with_context(|| format!("Failed to fetch supply of {}", mint))?;
+    Ok(supply.value)
+}
+
+// Баланс кастодиального аккаунта; None, если мост этот токен ни разу не принимал
+async fn custody_balance(rpc_url: &str, address: &str) -> Result<Option<u64>> {
+    let Some(info) = account::get_account_info(rpc_url, address).await? else {
+        return Ok(None);
+    };
+    let amount = account::parsed_info(&info)
+        .and_then(|info| info.pointer("/tokenAmount/amount"))
+        .and_then(Value::as_str)
+        .with_context(|| format!("Custody account {} is not a token account", address))?;
+    Ok(Some(amount.parse().with_context(|| format!("Invalid amount in custody account {}", address))?))
+}
+
+// Предложение на Solana, доли в мостах и остаток в обращении по каждому токену
+pub async fn run_report(rpc_url: &str, tokens: &[BridgedToken]) -> Result<()> {
+    if tokens.is_empty() {
+        anyhow::bail!("Config has no bridged_tokens");
+    }
+
+    for token in tokens {
+        let name = token.symbol.as_deref().unwrap_or(&token.mint);
+        let supply = get_supply(rpc_url, &token.mint).await?;
+        let total: u64 = supply.amount.parse().with_context(|| format!("Invalid supply of {}", token.mint))?;
+        println!("{}: supply on Solana {}", name, format::units(total, supply.decimals));
+
+        let mut locked = 0u64;
+        for adapter in ADAPTERS {
+            let custody = (adapter.custody)(adapter.program, &token.mint)?;
+            match custody_balance(rpc_url, &custody).await? {
+                Some(amount) => {
+                    locked += amount;
+                    let share = if total > 0 { amount as f64 / total as f64 * 100.0 } else { 0.0 };
+                    println!(
+                        "  {}: {} locked in {} ({:.2}% of supply)",
+                        adapter.name, format::units(amount, supply.decimals), custody, share
+                    );
+                }
+                None => println!("  {}: no custody account {}", adapter.name, custody),
+            }
+        }
+        // Заблокированное в мосте обращается в других сетях как обернутый токен
+        println!(
+            "  circulating on Solana: {}, bridged to other chains: {}",
+            format::units(total.saturating_sub(locked), supply.decimals),
+            format::units(locked, supply.decimals)
+        );
+    }
+
+    Ok(())
+}
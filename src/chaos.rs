@@ -0,0 +1,78 @@
+// src/chaos.rs
+// Внесение сбоев в RPC вызовы для проверки поведения при деградации (скрытый --inject-failures)
+use anyhow::{Context as AnyhowContext, Result};
+use rand::Rng;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// Параметры сбоев
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FailureInjection {
+    // Доля вызовов, завершающихся ошибкой
+    pub rate: f64,
+    // Максимальная случайная задержка перед каждым вызовом
+    pub latency: Duration,
+}
+
+// Настраивается один раз при запуске
+static INJECTION: OnceLock<FailureInjection> = OnceLock::new();
+
+impl FailureInjection {
+    // Разбор строки вида "rate=0.1,latency=500ms"
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut injection = FailureInjection::default();
+
+        for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            let (key, value) = part.split_once('=')
+                .with_context(|| format!("Expected key=value in --inject-failures, got '{}'", part))?;
+            match key.trim() {
+                "rate" => {
+                    injection.rate = value.trim().parse()
+                        .with_context(|| format!("Invalid failure rate: {}", value))?;
+                    if !(0.0..=1.0).contains(&injection.rate) {
+                        anyhow::bail!("Failure rate must be between 0 and 1, got {}", value);
+                    }
+                }
+                "latency" => injection.latency = parse_duration(value.trim())?,
+                other => anyhow::bail!("Unknown --inject-failures key: {} (expected rate, latency)", other),
+            }
+        }
+
+        Ok(injection)
+    }
+}
+
+// Длительность с суффиксом ms или s
+fn parse_duration(value: &str) -> Result<Duration> {
+    let invalid = || format!("Invalid duration: {} (expected e.g. 500ms or 2s)", value);
+
+    if let Some(ms) = value.strip_suffix("ms") {
+        return Ok(Duration::from_millis(ms.parse().with_context(invalid)?));
+    }
+    let seconds = value.strip_suffix('s').with_context(invalid)?;
+    Ok(Duration::from_secs_f64(seconds.parse().with_context(invalid)?))
+}
+
+// Включение сбоев; вызывается один раз из main
+pub fn set(injection: FailureInjection) {
+    println!("Failure injection enabled: rate {}, latency up to {:?}", injection.rate, injection.latency);
+    let _ = INJECTION.set(injection);
+}
+
+// Задержка и, с заданной вероятностью, ошибка перед RPC вызовом
+pub async fn before_call(method: &str) -> Result<()> {
+    let Some(injection) = INJECTION.get() else {
+        return Ok(());
+    };
+
+    let (delay, fail) = {
+        let mut rng = rand::thread_rng();
+        (injection.latency.mul_f64(rng.gen::<f64>()), rng.gen_bool(injection.rate))
+    };
+    tokio::time::sleep(delay).await;
+    if fail {
+        anyhow::bail!("Injected failure for {}", method);
+    }
+
+    Ok(())
+}
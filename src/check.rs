@@ -0,0 +1,59 @@
+// src/check.rs
+// Быстрая проверка одного адреса без конфигурации
+use anyhow::{Context as AnyhowContext, Result};
+use std::process::Command;
+
+use crate::account;
+use crate::address_audit;
+use crate::format;
+use crate::token;
+use crate::transaction;
+
+// Эндпоинт по умолчанию, когда конфигурации нет
+pub const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+// Утилиты чтения буфера обмена, пробуются по порядку
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbpaste", &[]),
+    ("wl-paste", &["--no-newline"]),
+    ("xclip", &["-selection", "clipboard", "-o"]),
+    ("xsel", &["--clipboard", "--output"]),
+    ("powershell", &["-NoProfile", "-Command", "Get-Clipboard"]),
+];
+
+// Текст из буфера обмена через первую доступную утилиту
+pub fn read_clipboard() -> Result<String> {
+    for (program, arguments) in CLIPBOARD_COMMANDS {
+        let Ok(output) = Command::new(program).args(*arguments).output() else {
+            continue;
+        };
+        if output.status.success() {
+            return String::from_utf8(output.stdout).context("Clipboard does not contain text");
+        }
+    }
+
+    anyhow::bail!("No clipboard utility found (tried pbpaste, wl-paste, xclip, xsel, powershell)")
+}
+
+// Проверка формата адреса и вывод баланса, владельца и токен-аккаунтов
+pub async fn run(rpc_url: &str, address: &str) -> Result<()> {
+    let address = address.trim();
+    let pubkey = transaction::decode_pubkey(address)?;
+    let kind = if address_audit::is_on_curve(&pubkey) { "wallet address (on curve)" } else { "off-curve address (PDA)" };
+    println!("{}: {}", address, kind);
+
+    let Some(info) = account::get_account_info(rpc_url, address).await? else {
+        println!("Account does not exist (0 SOL)");
+        return Ok(());
+    };
+    println!("Balance: {} SOL", format::sol(info.lamports));
+    println!("Owner: {}{}", info.owner, if info.executable { " (executable program)" } else { "" });
+
+    if info.owner == account::SYSTEM_PROGRAM {
+        let accounts = token::get_token_accounts(rpc_url, address).await?;
+        let non_empty = accounts.iter().filter(|account| account.amount > 0).count();
+        println!("Token accounts: {} ({} non-empty)", accounts.len(), non_empty);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// src/cleanup.rs
+// Поиск пустых токен-аккаунтов и закрытие их с возвратом ренты
+use anyhow::Result;
+use futures::future::join_all;
+
+use crate::format;
+use crate::signing::{self, SigningOptions};
+use crate::token::{self, TokenAccount};
+use crate::transaction;
+
+// Пустые токен-аккаунты, которые владелец может закрыть сам
+pub async fn find_closable(rpc_url: &str, wallet: &str) -> Result<Vec<TokenAccount>> {
+    let accounts = token::get_token_accounts(rpc_url, wallet).await?;
+
+    Ok(accounts
+        .into_iter()
+        .filter(|account| account.amount == 0 && account.state != "frozen")
+        .filter(|account| account.close_authority.as_deref().is_none_or(|authority| authority == wallet))
+        .collect())
+}
+
+// Отчет по пустым аккаунтам и выдача транзакций CloseAccount
+pub async fn run(rpc_url: &str, wallet: &str, report_only: bool, options: &SigningOptions<'_>) -> Result<()> {
+    let closable = find_closable(rpc_url, wallet).await?;
+    let reclaimable: u64 = closable.iter().map(|account| account.lamports).sum();
+
+    println!(
+        "Empty token accounts of {}: {}, reclaimable rent {} SOL",
+        wallet,
+        closable.len(),
+        format::sol(reclaimable)
+    );
+    for account in &closable {
+        println!(
+            "  {} (mint {}): {} SOL",
+            account.address,
+            account.mint,
+            format::sol(account.lamports)
+        );
+    }
+    if closable.is_empty() || report_only {
+        return Ok(());
+    }
+
+    // Рента возвращается на сам кошелек
+    let owner = transaction::decode_pubkey(wallet)?;
+    let instructions = closable
+        .iter()
+        .map(|account| token::close_account_instruction(account, &owner))
+        .collect::<Result<Vec<_>>>()?;
+
+    signing::deliver(rpc_url, wallet, instructions, options).await
+}
+
+// Сводка по ренте в пустых аккаунтах всех кошельков
+pub async fn rent_report(rpc_url: &str, wallets: &[String]) -> Result<()> {
+    let results = join_all(wallets.iter().map(|wallet| find_closable(rpc_url, wallet))).await;
+
+    println!("Reclaimable rent for {} wallets:", wallets.len());
+    let (mut total_accounts, mut total_lamports) = (0, 0);
+    for (wallet, result) in wallets.iter().zip(results) {
+        match result {
+            Ok(closable) => {
+                let lamports: u64 = closable.iter().map(|account| account.lamports).sum();
+                total_accounts += closable.len();
+                total_lamports += lamports;
+                println!("{}: {} empty accounts, {} SOL", wallet, closable.len(), format::sol(lamports));
+            }
+            Err(e) => println!("Error fetching token accounts for wallet {}: {:#}", wallet, e),
+        }
+    }
+    println!("Total: {} empty accounts, {} SOL", total_accounts, format::sol(total_lamports));
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// src/components.rs
+// Баланс кошелька по составляющим в SOL: свободные, застейканные, заблокированные
+use anyhow::Result;
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::dex;
+use crate::format;
+use crate::lst;
+use crate::staking;
+use crate::vesting;
+
+// Кошельков, запрашиваемых одновременно: на каждый приходится несколько запросов к узлу
+const CONCURRENT_WALLETS: usize = 8;
+
+// Составляющие баланса в лампортах; стоимость прочих токенов и NFT не считается - нет источника цен
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct Components {
+    pub liquid_lamports: u64,
+    // Нативный стейк и токены ликвидного стейкинга по курсу пула
+    pub staked_lamports: u64,
+    // Wrapped SOL в ордерах на DEX и еще не разблокированный в контрактах вестинга
+    pub locked_lamports: u64,
+}
+
+impl Components {
+    pub fn total(&self) -> u64 {
+        self.liquid_lamports + self.staked_lamports + self.locked_lamports
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "liquid {} SOL, staked {} SOL, locked {} SOL, total {} SOL",
+            format::balance(self.liquid_lamports),
+            format::balance(self.staked_lamports),
+            format::balance(self.locked_lamports),
+            format::balance(self.total())
+        )
+    }
+}
+
+async fn wallet_components(rpc_url: &str, wallet: &str, rates: &HashMap<&'static str, lst::ExchangeRate>) -> Result<Components> {
+    let breakdown = staking::wallet_breakdown(rpc_url, wallet, rates).await?;
+    let vesting_locked: u64 = vesting::get_vesting_contracts(rpc_url, wallet, &breakdown.token_accounts, Utc::now().timestamp())
+        .await?
+        .iter()
+        .filter(|contract| contract.mint == dex::WRAPPED_SOL_MINT)
+        .map(|contract| contract.locked)
+        .sum();
+
+    Ok(Components {
+        liquid_lamports: breakdown.liquid,
+        staked_lamports: breakdown.staked(),
+        locked_lamports: breakdown.dex_locked + vesting_locked,
+    })
+}
+
+// Составляющие для каждого кошелька; кошелек с ошибкой пропускается с сообщением
+pub async fn get_components(rpc_url: &str, wallets: &[String]) -> HashMap<String, Components> {
+    let rates = staking::get_rates(rpc_url).await;
+    let rates = &rates;
+    let results: Vec<Result<Components>> = stream::iter(wallets)
+        .map(|wallet| wallet_components(rpc_url, wallet, rates))
+        .buffered(CONCURRENT_WALLETS)
+        .collect()
+        .await;
+
+    let mut components = HashMap::new();
+    for (wallet, result) in wallets.iter().zip(results) {
+        match result {
+            Ok(found) => {
+                components.insert(wallet.clone(), found);
+            }
+            Err(e) => println!("Error fetching balance components for wallet {}: {:#}", wallet, e),
+        }
+    }
+    components
+}
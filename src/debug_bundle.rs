@@ -0,0 +1,189 @@
+// src/debug_bundle.rs
+// Каталог для отчета об ошибке: конфигурация и аргументы без секретов, итог запуска, журнал RPC запросов
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// Имена ключей конфигурации и параметров URL, значения которых считаются секретами (отчет об ошибке и lint)
+const SECRET_NAMES: &[&str] = &[
+    "api-key", "api_key", "apikey", "key", "auth", "token", "access_token", "secret", "password", "private",
+];
+
+// Секрет - имя из списка или имя с таким словом через _ или - (helius_api_key, auth);
+// длинные имена ищутся и внутри слов (clientSecret, privateKey)
+pub fn is_secret_name(name: &str) -> bool {
+    let name = name.trim().to_lowercase();
+    name.split(['_', '-']).any(|word| SECRET_NAMES.contains(&word))
+        || SECRET_NAMES.iter().filter(|secret| secret.len() > 4).any(|secret| name.contains(secret))
+}
+
+// Запрос, завершившийся ошибкой
+#[derive(Debug, Serialize)]
+struct FailedRequest {
+    time: String,
+    endpoint: String,
+    method: String,
+    elapsed_ms: u128,
+    error: String,
+}
+
+// Время и число запросов метода
+#[derive(Debug, Default, Serialize)]
+struct MethodTiming {
+    requests: u64,
+    failures: u64,
+    total_ms: u128,
+    max_ms: u128,
+}
+
+struct Recorder {
+    started: Instant,
+    started_at: String,
+    timings: BTreeMap<String, MethodTiming>,
+    failures: Vec<FailedRequest>,
+}
+
+// Запись включается флагом --debug-bundle
+static RECORDER: OnceLock<Mutex<Recorder>> = OnceLock::new();
+
+pub fn enable() {
+    let recorder = Recorder {
+        started: Instant::now(),
+        started_at: Utc::now().to_rfc3339(),
+        timings: BTreeMap::new(),
+        failures: Vec::new(),
+    };
+    let _ = RECORDER.set(Mutex::new(recorder));
+}
+
+// Учет завершенного RPC запроса; без --debug-bundle ничего не делает
+pub fn record(endpoint: &str, method: &str, elapsed: Duration, error: Option<&anyhow::Error>) {
+    let Some(recorder) = RECORDER.get() else {
+        return;
+    };
+    let mut recorder = recorder.lock().unwrap();
+
+    let timing = recorder.timings.entry(method.to_string()).or_default();
+    timing.requests += 1;
+    timing.total_ms += elapsed.as_millis();
+    timing.max_ms = timing.max_ms.max(elapsed.as_millis());
+    if let Some(error) = error {
+        timing.failures += 1;
+        recorder.failures.push(FailedRequest {
+            time: Utc::now().to_rfc3339(),
+            endpoint: scrub(endpoint),
+            method: method.to_string(),
+            elapsed_ms: elapsed.as_millis(),
+            error: scrub(&format!("{:#}", error)),
+        });
+    }
+}
+
+// URL в тексте: остаются схема и хост, учетные данные, путь и параметры (там бывают ключи API) скрываются
+pub fn scrub(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("://") {
+        output.push_str(&rest[..start + 3]);
+        let url = &rest[start + 3..];
+        let end = url.find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ')' | '>' | ',')).unwrap_or(url.len());
+        let authority_end = url[..end].find(['/', '?', '#']).unwrap_or(end);
+        let host = url[..authority_end].rsplit('@').next().unwrap_or_default();
+        output.push_str(host);
+        if url[authority_end..end].trim_start_matches('/').is_empty() {
+            output.push_str(&url[authority_end..end]);
+        } else {
+            output.push_str("/<redacted>");
+        }
+        rest = &url[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+// Конфигурация без секретов: URL сокращаются, значения секретных ключей заменяются
+fn scrub_config(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let line = scrub(line);
+            match line.split_once(':') {
+                Some((key, value))
+                    if !value.trim().is_empty()
+                        && !key.trim_start().starts_with('#')
+                        && is_secret_name(key.trim_start().trim_start_matches("- ")) =>
+                {
+                    format!("{}: \"<redacted>\"", key)
+                }
+                _ => line,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+// Итог запуска
+#[derive(Debug, Serialize)]
+struct RunSummary {
+    version: &'static str,
+    os: &'static str,
+    arguments: Vec<String>,
+    started_at: String,
+    wall_ms: u128,
+    error: Option<String>,
+}
+
+// Каталог создается заново и только для владельца: в нем журнал запросов и конфигурация
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(path)
+}
+
+// Запись каталога с файлами отчета; секреты вычищаются до записи на диск,
+// при ошибке частично записанный каталог удаляется
+pub fn write(path: &Path, config_path: &Path, result: &Result<()>) -> Result<()> {
+    let recorder = RECORDER.get().context("Debug bundle recording was not enabled")?.lock().unwrap();
+
+    let summary = RunSummary {
+        version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        arguments: std::env::args().map(|argument| scrub(&argument)).collect(),
+        started_at: recorder.started_at.clone(),
+        wall_ms: recorder.started.elapsed().as_millis(),
+        error: result.as_ref().err().map(|e| scrub(&format!("{:#}", e))),
+    };
+    let config = match fs::read_to_string(config_path) {
+        Ok(text) => scrub_config(&text),
+        Err(e) => format!("# Config {:?} could not be read: {}", config_path, e),
+    };
+    let files = [
+        ("run.json", serde_json::to_string_pretty(&summary)?),
+        ("config.yaml", config),
+        ("timings.json", serde_json::to_string_pretty(&recorder.timings)?),
+        ("failed-requests.json", serde_json::to_string_pretty(&recorder.failures)?),
+    ];
+
+    // Существующий каталог не перезаписывается
+    create_private_dir(path).with_context(|| format!("Failed to create {:?}", path))?;
+    for (name, content) in &files {
+        if let Err(e) = fs::write(path.join(name), content) {
+            let _ = fs::remove_dir_all(path);
+            return Err(e).with_context(|| format!("Failed to write {} to {:?}", name, path));
+        }
+    }
+    println!(
+        "Debug bundle written to {:?}: {} requests, {} failed",
+        path,
+        recorder.timings.values().map(|timing| timing.requests).sum::<u64>(),
+        recorder.failures.len()
+    );
+
+    Ok(())
+}
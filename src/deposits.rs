@@ -0,0 +1,170 @@
+// src/deposits.rs
+// Ожидание зарегистрированных входящих платежей на отслеживаемые кошельки
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::format;
+use crate::solana_pay;
+use crate::tx;
+
+// Файл с ожидаемыми платежами
+#[derive(Debug, Deserialize)]
+struct DepositsFile {
+    deposits: Vec<ExpectedDeposit>,
+}
+
+// Ожидаемый платеж: поиск по reference или по тексту memo
+#[derive(Debug, Deserialize)]
+pub struct ExpectedDeposit {
+    pub id: String,
+    pub wallet: String,
+    // Сумма в SOL (или в единицах токена при указании spl_token)
+    pub amount: String,
+    pub spl_token: Option<String>,
+    pub reference: Option<String>,
+    pub memo: Option<String>,
+    pub deadline: DateTime<Utc>,
+}
+
+// Итог проверки платежа
+#[derive(Debug)]
+enum DepositStatus {
+    Pending,
+    Confirmed { signature: String, received: u64 },
+    Mismatched { signature: String, received: u64, expected: u64 },
+    // Платеж найден, но пришел после deadline
+    Late { signature: String, received: u64, block_time: i64 },
+    TimedOut,
+}
+
+// Загрузка ожидаемых платежей и проверка, что они относятся к отслеживаемым кошелькам
+pub fn load(path: &Path, tracked: &[String]) -> Result<Vec<ExpectedDeposit>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open deposits file: {:?}", path))?;
+
+    let parsed: DepositsFile = serde_yaml::from_reader(file)
+        .with_context(|| "Failed to parse deposits file")?;
+
+    for deposit in &parsed.deposits {
+        if !tracked.contains(&deposit.wallet) {
+            anyhow::bail!("Deposit {} targets wallet {} which is not tracked", deposit.id, deposit.wallet);
+        }
+        if deposit.reference.is_none() && deposit.memo.is_none() {
+            anyhow::bail!("Deposit {} needs a reference or a memo to be matched", deposit.id);
+        }
+        if deposit.spl_token.is_none() {
+            solana_pay::parse_decimal_amount(&deposit.amount, solana_pay::SOL_DECIMALS)
+                .with_context(|| format!("Invalid amount for deposit {}", deposit.id))?;
+        }
+    }
+
+    Ok(parsed.deposits)
+}
+
+// Опрос до тех пор, пока все платежи не будут подтверждены, отклонены или просрочены
+pub async fn watch(
+    rpc_url: &str,
+    deposits: &[ExpectedDeposit],
+    interval: Duration,
+    scan_limit: usize,
+) -> Result<()> {
+    println!("Watching {} expected deposits", deposits.len());
+
+    let mut pending: Vec<&ExpectedDeposit> = deposits.iter().collect();
+    let (mut confirmed, mut mismatched, mut late, mut timed_out) = (0, 0, 0, 0);
+
+    while !pending.is_empty() {
+        let mut still_pending = Vec::new();
+
+        for deposit in pending {
+            let status = match check(rpc_url, deposit, scan_limit).await {
+                Ok(status) => status,
+                Err(e) => {
+                    println!("Error checking deposit {}: {:#}", deposit.id, e);
+                    DepositStatus::Pending
+                }
+            };
+
+            match status {
+                DepositStatus::Pending => still_pending.push(deposit),
+                DepositStatus::Confirmed { signature, received } => {
+                    confirmed += 1;
+                    println!("[CONFIRMED] {}: {} received {} base units in {}", deposit.id, deposit.wallet, received, signature);
+                }
+                DepositStatus::Mismatched { signature, received, expected } => {
+                    mismatched += 1;
+                    println!(
+                        "[MISMATCH] {}: {} received {} base units, expected {} ({})",
+                        deposit.id, deposit.wallet, received, expected, signature
+                    );
+                }
+                DepositStatus::Late { signature, received, block_time } => {
+                    late += 1;
+                    println!(
+                        "[LATE] {}: {} received {} base units at {}, after the deadline {} ({})",
+                        deposit.id, deposit.wallet, received, format::time(block_time), deposit.deadline, signature
+                    );
+                }
+                DepositStatus::TimedOut => {
+                    timed_out += 1;
+                    println!("[TIMED OUT] {}: no payment before {}", deposit.id, deposit.deadline);
+                }
+            }
+        }
+
+        pending = still_pending;
+        if !pending.is_empty() {
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    println!("Deposits: {} confirmed, {} mismatched, {} late, {} timed out", confirmed, mismatched, late, timed_out);
+    if mismatched + late + timed_out > 0 {
+        anyhow::bail!("{} deposits need manual reconciliation", mismatched + late + timed_out);
+    }
+
+    Ok(())
+}
+
+// Однократная проверка ожидаемого платежа
+async fn check(rpc_url: &str, deposit: &ExpectedDeposit, scan_limit: usize) -> Result<DepositStatus> {
+    // По reference ищем среди транзакций самого reference, по memo - среди транзакций кошелька
+    let signatures = match &deposit.reference {
+        Some(reference) => tx::get_signatures(rpc_url, reference, scan_limit).await?,
+        None => tx::get_signatures(rpc_url, &deposit.wallet, scan_limit).await?,
+    };
+
+    let found = signatures.into_iter().find(|info| {
+        info.err.is_none()
+            && deposit.memo.as_ref().is_none_or(|memo| info.memos().contains(&memo.as_str()))
+    });
+
+    let Some(found) = found else {
+        if Utc::now() > deposit.deadline {
+            return Ok(DepositStatus::TimedOut);
+        }
+        return Ok(DepositStatus::Pending);
+    };
+
+    let transaction = tx::get_transaction(rpc_url, &found.signature).await?;
+    let (received, expected) = solana_pay::received_and_expected(
+        &transaction,
+        &deposit.wallet,
+        &deposit.amount,
+        deposit.spl_token.as_deref(),
+    )?;
+
+    if !solana_pay::amount_accepted(received, expected) {
+        return Ok(DepositStatus::Mismatched { signature: found.signature, received, expected });
+    }
+    match found.block_time {
+        Some(block_time) if block_time > deposit.deadline.timestamp() => {
+            Ok(DepositStatus::Late { signature: found.signature, received, block_time })
+        }
+        _ => Ok(DepositStatus::Confirmed { signature: found.signature, received }),
+    }
+}
@@ -0,0 +1,160 @@
+// src/dex.rs
+// Средства в открытых ордерах OpenBook (формат Serum), принадлежащих кошельку
+use anyhow::{Context as AnyhowContext, Result};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::account::{self, KeyedAccount};
+use crate::format;
+use crate::rpc;
+use crate::token;
+
+pub const OPENBOOK_PROGRAM: &str = "srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX";
+pub const WRAPPED_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+// Аккаунт OpenOrders: 5 байт заголовка "serum", флаги, market, owner, затем суммы
+const OPEN_ORDERS_SIZE: u64 = 3228;
+const OPEN_ORDERS_MARKET: usize = 13;
+const OPEN_ORDERS_OWNER: usize = 45;
+const OPEN_ORDERS_BASE_FREE: usize = 77;
+const OPEN_ORDERS_BASE_TOTAL: usize = 85;
+const OPEN_ORDERS_QUOTE_FREE: usize = 93;
+const OPEN_ORDERS_QUOTE_TOTAL: usize = 101;
+
+// Mint базового и котируемого токена в аккаунте рынка
+const MARKET_BASE_MINT: usize = 53;
+const MARKET_QUOTE_MINT: usize = 85;
+
+// Средства кошелька на одном рынке: total включает free (доступное к выводу через settle)
+#[derive(Debug)]
+pub struct OpenOrders {
+    pub address: String,
+    pub market: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub base_total: u64,
+    pub base_free: u64,
+    pub quote_total: u64,
+    pub quote_free: u64,
+}
+
+// Аккаунты OpenOrders кошелька с ненулевыми суммами
+pub async fn get_open_orders(rpc_url: &str, wallet: &str) -> Result<Vec<OpenOrders>> {
+    let accounts: Vec<KeyedAccount> = rpc::call(
+        rpc_url,
+        "getProgramAccounts",
+        json!([OPENBOOK_PROGRAM, {
+            "encoding": "base64",
+            "filters": [
+                { "dataSize": OPEN_ORDERS_SIZE },
+                { "memcmp": { "offset": OPEN_ORDERS_OWNER, "bytes": wallet } }
+            ]
+        }]),
+    )
+    .await
+    .with_context(|| format!("Failed to fetch open orders of {}", wallet))?;
+
+    let mut markets: HashMap<String, (String, String)> = HashMap::new();
+    let mut orders = Vec::new();
+    for keyed in accounts {
+        let data = account::raw_data(&keyed.account)
+            .with_context(|| format!("Account {} has invalid base64 data", keyed.pubkey))?;
+        let field = |offset| account::read_u64(&data, offset)
+            .with_context(|| format!("Open orders account {} is too short", keyed.pubkey));
+
+        let (base_total, quote_total) = (field(OPEN_ORDERS_BASE_TOTAL)?, field(OPEN_ORDERS_QUOTE_TOTAL)?);
+        if base_total == 0 && quote_total == 0 {
+            continue;
+        }
+
+        let market = account::read_pubkey(&data, OPEN_ORDERS_MARKET)
+            .with_context(|| format!("Open orders account {} has no market", keyed.pubkey))?;
+        if !markets.contains_key(&market) {
+            markets.insert(market.clone(), market_mints(rpc_url, &market).await?);
+        }
+        let (base_mint, quote_mint) = markets[&market].clone();
+
+        orders.push(OpenOrders {
+            address: keyed.pubkey.clone(),
+            market,
+            base_mint,
+            quote_mint,
+            base_total,
+            base_free: field(OPEN_ORDERS_BASE_FREE)?,
+            quote_total,
+            quote_free: field(OPEN_ORDERS_QUOTE_FREE)?,
+        });
+    }
+
+    Ok(orders)
+}
+
+// Заблокированные на DEX лампорты: wrapped SOL в открытых ордерах
+pub fn locked_lamports(orders: &[OpenOrders]) -> u64 {
+    orders
+        .iter()
+        .map(|order| {
+            let base = if order.base_mint == WRAPPED_SOL_MINT { order.base_total } else { 0 };
+            let quote = if order.quote_mint == WRAPPED_SOL_MINT { order.quote_total } else { 0 };
+            base + quote
+        })
+        .sum()
+}
+
+// Mint базового и котируемого токена рынка
+async fn market_mints(rpc_url: &str, market: &str) -> Result<(String, String)> {
+    let data = account::get_account_data(rpc_url, market).await?;
+    let mint = |offset| account::read_pubkey(&data, offset)
+        .with_context(|| format!("Market account {} is too short", market));
+
+    Ok((mint(MARKET_BASE_MINT)?, mint(MARKET_QUOTE_MINT)?))
+}
+
+// Отчет по всем кошелькам: суммы по каждому рынку и итог "locked on DEX" по токенам
+pub async fn run_report(rpc_url: &str, wallets: &[String]) -> Result<()> {
+    let mut totals: BTreeMap<String, u64> = BTreeMap::new();
+    let mut decimals: HashMap<String, u32> = HashMap::new();
+
+    for wallet in wallets {
+        println!("Open orders of {}:", wallet);
+        let orders = match get_open_orders(rpc_url, wallet).await {
+            Ok(orders) => orders,
+            Err(e) => {
+                println!("  Error: {:#}", e);
+                continue;
+            }
+        };
+        if orders.is_empty() {
+            println!("  no funds in open orders");
+        }
+
+        for order in &orders {
+            for mint in [&order.base_mint, &order.quote_mint] {
+                if !decimals.contains_key(mint) {
+                    decimals.insert(mint.clone(), token::mint_decimals(rpc_url, mint).await?);
+                }
+            }
+            let (base, quote) = (decimals[&order.base_mint], decimals[&order.quote_mint]);
+            println!(
+                "  market {} ({}): base {} ({} free) {}, quote {} ({} free) {}",
+                order.market,
+                order.address,
+                format::units(order.base_total, base),
+                format::units(order.base_free, base),
+                order.base_mint,
+                format::units(order.quote_total, quote),
+                format::units(order.quote_free, quote),
+                order.quote_mint
+            );
+            *totals.entry(order.base_mint.clone()).or_default() += order.base_total;
+            *totals.entry(order.quote_mint.clone()).or_default() += order.quote_total;
+        }
+    }
+
+    println!("Locked on DEX:");
+    for (mint, amount) in totals.iter().filter(|(_, amount)| **amount > 0) {
+        println!("  {}: {}", mint, format::units(*amount, decimals[mint]));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// src/discovery.rs
+// Поиск дополнительных RPC узлов кластера через getClusterNodes
+use anyhow::{Context as AnyhowContext, Result};
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::rpc;
+
+// Секция node_discovery конфигурации
+#[derive(Debug, Deserialize)]
+pub struct DiscoveryConfig {
+    // Узел, у которого запрашивается список; по умолчанию rpc_url
+    pub seed: Option<String>,
+    // Сколько найденных узлов использовать вместе с основным эндпоинтом
+    #[serde(default = "default_max_nodes")]
+    pub max_nodes: usize,
+    // Узлы, не ответившие на getHealth за это время, не используются
+    #[serde(default = "default_health_timeout")]
+    pub health_timeout_secs: u64,
+}
+
+fn default_max_nodes() -> usize {
+    4
+}
+
+fn default_health_timeout() -> u64 {
+    2
+}
+
+// Сколько узлов проверяется одновременно
+const CONCURRENT_CHECKS: usize = 32;
+
+// Запись из ответа getClusterNodes
+#[derive(Debug, Deserialize)]
+struct ClusterNode {
+    // Адрес RPC вида "ip:port", если узел его публикует
+    rpc: Option<String>,
+}
+
+// RPC узлы кластера, прошедшие проверку getHealth; не больше max_nodes
+pub async fn discover(rpc_url: &str, config: &DiscoveryConfig) -> Result<Vec<String>> {
+    let seed = config.seed.as_deref().unwrap_or(rpc_url);
+    let nodes: Vec<ClusterNode> = rpc::call(seed, "getClusterNodes", json!([]))
+        .await
+        .with_context(|| format!("Failed to fetch cluster nodes from {}", seed))?;
+
+    let candidates: Vec<String> = nodes
+        .into_iter()
+        .filter_map(|node| Some(format!("http://{}", node.rpc?)))
+        .filter(|url| url != rpc_url)
+        .collect();
+
+    // Проверка останавливается, как только набрано max_nodes рабочих узлов
+    let timeout = Duration::from_secs(config.health_timeout_secs);
+    let healthy: Vec<String> = stream::iter(candidates)
+        .map(|url| async move {
+            let check = tokio::time::timeout(timeout, rpc::call::<String>(&url, "getHealth", json!([]))).await;
+            matches!(check, Ok(Ok(_))).then_some(url)
+        })
+        .buffer_unordered(CONCURRENT_CHECKS)
+        .filter_map(|url| async move { url })
+        .take(config.max_nodes)
+        .collect()
+        .await;
+    println!("Discovered {} healthy RPC nodes via {}", healthy.len(), seed);
+
+    Ok(healthy)
+}
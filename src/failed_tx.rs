@@ -0,0 +1,64 @@
+// src/failed_tx.rs
+// Неуспешные транзакции, оплаченные отслеживаемыми кошельками, с ошибкой и логами программ
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::flows;
+use crate::format;
+use crate::tx;
+
+// Сколько последних строк лога выводится для каждой транзакции
+const LOG_TAIL: usize = 10;
+
+// Проверка последних limit транзакций каждого кошелька; только не старше since (unix time), если задано
+pub async fn run_report(rpc_url: &str, wallets: &[String], limit: usize, since: Option<i64>) -> Result<()> {
+    let mut alerts = 0;
+
+    for wallet in wallets {
+        let signatures = tx::get_signatures(rpc_url, wallet, limit).await?;
+        let failed: Vec<&tx::SignatureInfo> = signatures
+            .iter()
+            .filter(|info| info.err.is_some())
+            .filter(|info| match (since, info.block_time) {
+                (Some(since), Some(time)) => time >= since,
+                _ => true,
+            })
+            .collect();
+
+        for (info, transaction) in tx::get_transactions(rpc_url, failed).await {
+            let transaction = match transaction {
+                Ok(transaction) => transaction,
+                Err(e) => {
+                    println!("Skipping transaction {}: {:#}", info.signature, e);
+                    continue;
+                }
+            };
+            // Кошелек мог участвовать в чужой неуспешной транзакции; важны только оплаченные им
+            let payer = flows::account_keys(&transaction)?.into_iter().next();
+            if payer.as_ref() != Some(wallet) {
+                continue;
+            }
+
+            alerts += 1;
+            let time = info.block_time.map_or_else(|| "unknown time".to_string(), format::time);
+            let error = info.err.as_ref().map_or_else(String::new, Value::to_string);
+            println!("[ALERT] {} failed transaction {} at {} (slot {}): {}", wallet, info.signature, time, info.slot, error);
+
+            let logs: Vec<&str> = transaction
+                .pointer("/meta/logMessages")
+                .and_then(Value::as_array)
+                .map(|logs| logs.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            for line in &logs[logs.len().saturating_sub(LOG_TAIL)..] {
+                println!("    {}", line);
+            }
+        }
+    }
+
+    if alerts > 0 {
+        anyhow::bail!("{} failed transactions paid by tracked wallets", alerts);
+    }
+    println!("No failed transactions paid by {} tracked wallets", wallets.len());
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// src/fees.rs
+// Комиссии, уплаченные кошельками-плательщиками: расход по дням и неделям, всплески и бюджет
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::flows;
+use crate::format;
+use crate::tx;
+
+// Секция fee_payers конфигурации
+#[derive(Debug, Deserialize)]
+pub struct FeePayersConfig {
+    pub wallets: Vec<String>,
+    // Всплеск: расход за последний день больше среднего за предыдущие дни в это число раз
+    #[serde(default = "default_spike_factor")]
+    pub spike_factor: f64,
+    // Лимит комиссий одного плательщика в сутки (в часовом поясе вывода) в лампортах
+    pub daily_budget_lamports: Option<u64>,
+}
+
+fn default_spike_factor() -> f64 {
+    3.0
+}
+
+// Комиссия транзакции, если ее оплатил payer (первый ключ транзакции)
+fn paid_fee(transaction: &Value, payer: &str) -> Result<Option<u64>> {
+    let keys = flows::account_keys(transaction)?;
+    if keys.first().map(String::as_str) != Some(payer) {
+        return Ok(None);
+    }
+    Ok(transaction.pointer("/meta/fee").and_then(Value::as_u64))
+}
+
+// Комиссии плательщика по дням (в часовом поясе вывода) за последние days дней
+async fn daily_fees(rpc_url: &str, payer: &str, days: u32) -> Result<BTreeMap<NaiveDate, (u64, usize)>> {
+    let since = Utc::now() - Duration::days(i64::from(days));
+    let signatures = tx::get_signatures_since(rpc_url, payer, since.timestamp()).await?;
+
+    // Комиссия списывается и с неуспешных транзакций
+    let mut by_day: BTreeMap<NaiveDate, (u64, usize)> = BTreeMap::new();
+    for (info, transaction) in tx::get_transactions(rpc_url, signatures.iter().collect()).await {
+        let fee = match transaction.and_then(|transaction| paid_fee(&transaction, payer)) {
+            Ok(Some(fee)) => fee,
+            Ok(None) => continue,
+            Err(e) => {
+                println!("Skipping transaction {}: {:#}", info.signature, e);
+                continue;
+            }
+        };
+        let day = format::date(info.block_time.unwrap_or_else(|| Utc::now().timestamp()));
+        let entry = by_day.entry(day).or_default();
+        entry.0 += fee;
+        entry.1 += 1;
+    }
+
+    Ok(by_day)
+}
+
+// Отчет по всем плательщикам; всплески и превышение бюджета выводятся как [ALERT]
+pub async fn run_report(rpc_url: &str, config: &FeePayersConfig, days: u32) -> Result<()> {
+    let mut alerts = 0;
+    let today = format::date(Utc::now().timestamp());
+
+    for payer in &config.wallets {
+        let by_day = daily_fees(rpc_url, payer, days).await?;
+        let total: u64 = by_day.values().map(|(fees, _)| fees).sum();
+        let count: usize = by_day.values().map(|(_, count)| count).sum();
+        println!("{}: {} SOL in fees over {} transactions, last {} days", payer, format::sol(total), count, days);
+
+        println!("  Daily:");
+        for (day, (fees, count)) in &by_day {
+            println!("    {}: {} SOL ({} transactions)", day, format::sol(*fees), count);
+        }
+
+        let mut by_week: BTreeMap<(i32, u32), u64> = BTreeMap::new();
+        for (day, (fees, _)) in &by_day {
+            let week = day.iso_week();
+            *by_week.entry((week.year(), week.week())).or_default() += fees;
+        }
+        println!("  Weekly:");
+        for ((year, week), fees) in &by_week {
+            println!("    {}-W{:02}: {} SOL", year, week, format::sol(*fees));
+        }
+
+        // Сегодняшний расход сравнивается со средним за остальные дни периода
+        let today_fees = by_day.get(&today).map_or(0, |(fees, _)| *fees);
+        let previous_days = days.saturating_sub(1).max(1);
+        let average = (total - today_fees) as f64 / f64::from(previous_days);
+        if today_fees > 0 && average > 0.0 && today_fees as f64 > average * config.spike_factor {
+            alerts += 1;
+            println!(
+                "[ALERT] {} fee spike: {} SOL today vs {} SOL daily average",
+                payer, format::sol(today_fees), format::sol(average.round() as u64)
+            );
+        }
+        if let Some(budget) = config.daily_budget_lamports {
+            for (day, (fees, _)) in by_day.iter().filter(|(_, (fees, _))| *fees > budget) {
+                alerts += 1;
+                println!(
+                    "[ALERT] {} spent {} SOL in fees on {}, over the daily budget of {} SOL",
+                    payer, format::sol(*fees), day, format::sol(budget)
+                );
+            }
+        }
+    }
+
+    if alerts > 0 {
+        anyhow::bail!("Fee report raised {} alerts", alerts);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+// src/fetch_groups.rs
+// Запрос балансов по группам: у каждой группы свой параллелизм и бюджет повторов,
+// ошибки и задержки одной группы не отнимают ресурсы у других
+use anyhow::Result;
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::groups::Groups;
+use crate::progress;
+use crate::run_stats;
+use crate::{get_single_balance, WalletBalance};
+
+// Элемент секции fetch_groups
+#[derive(Debug, Deserialize)]
+pub struct FetchGroupConfig {
+    pub group: String,
+    // Сколько запросов группы выполняется одновременно
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    // Сколько повторов после ошибок допускается на всю группу за запуск
+    #[serde(default)]
+    pub retry_budget: u32,
+}
+
+fn default_concurrency() -> usize {
+    16
+}
+
+// Пауза перед повтором, чтобы не добивать перегруженный эндпоинт
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+// Часть кошельков с собственными ограничениями; индексы - позиции в общем списке wallets
+struct Partition {
+    name: String,
+    members: Vec<(usize, String)>,
+    concurrency: usize,
+    retry_budget: u32,
+}
+
+// Кошелек попадает в первую подходящую группу; остальные запрашиваются все сразу без повторов, как без fetch_groups
+fn partition(wallets: &[String], groups: &Groups, configs: &[FetchGroupConfig]) -> Result<Vec<Partition>> {
+    let mut assigned = vec![false; wallets.len()];
+    let mut partitions = Vec::new();
+    for config in configs {
+        let members_of_group = groups.resolve(&config.group)?;
+        let mut members = Vec::new();
+        for (i, wallet) in wallets.iter().enumerate() {
+            if !assigned[i] && members_of_group.contains(wallet) {
+                assigned[i] = true;
+                members.push((i, wallet.clone()));
+            }
+        }
+        partitions.push(Partition {
+            name: config.group.clone(),
+            members,
+            concurrency: config.concurrency.max(1),
+            retry_budget: config.retry_budget,
+        });
+    }
+
+    let rest: Vec<(usize, String)> =
+        wallets.iter().enumerate().filter(|(i, _)| !assigned[*i]).map(|(i, wallet)| (i, wallet.clone())).collect();
+    if !rest.is_empty() {
+        partitions.push(Partition { name: "other".to_string(), concurrency: rest.len(), members: rest, retry_budget: 0 });
+    }
+    Ok(partitions)
+}
+
+async fn fetch_partition(endpoints: &[String], partition: &Partition) -> Vec<(usize, WalletBalance)> {
+    let started = Instant::now();
+    let budget = AtomicU32::new(partition.retry_budget);
+    let budget = &budget;
+
+    let results: Vec<Option<(usize, WalletBalance)>> = stream::iter(&partition.members)
+        .map(|(i, wallet)| async move {
+            let rpc_url = &endpoints[i % endpoints.len()];
+            loop {
+                match get_single_balance(rpc_url, wallet).await {
+                    Ok(balance) => {
+                        progress::wallet_done(&balance.address, balance.lamports, balance.slot);
+                        return Some((*i, balance));
+                    }
+                    Err(e) => {
+                        if budget.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| left.checked_sub(1)).is_ok() {
+                            run_stats::retry();
+                            tokio::time::sleep(RETRY_DELAY).await;
+                            continue;
+                        }
+                        println!("Error fetching balance for wallet {} (group {}): {:#}", wallet, partition.name, e);
+                        progress::error(Some(wallet), &e);
+                        return None;
+                    }
+                }
+            }
+        })
+        .buffer_unordered(partition.concurrency)
+        .collect()
+        .await;
+
+    let fetched: Vec<(usize, WalletBalance)> = results.into_iter().flatten().collect();
+    println!(
+        "Group {}: {}/{} wallets in {:.2}s, {} of {} retries used",
+        partition.name,
+        fetched.len(),
+        partition.members.len(),
+        started.elapsed().as_secs_f64(),
+        partition.retry_budget - budget.load(Ordering::Relaxed),
+        partition.retry_budget
+    );
+    fetched
+}
+
+// Балансы всех кошельков в порядке wallets; группы запрашиваются одновременно, каждая в своих пределах
+pub async fn get_wallet_balances(
+    endpoints: &[String],
+    wallets: &[String],
+    groups: &Groups<'_>,
+    configs: &[FetchGroupConfig],
+) -> Result<Vec<WalletBalance>> {
+    let partitions = partition(wallets, groups, configs)?;
+    let mut fetched: Vec<(usize, WalletBalance)> =
+        join_all(partitions.iter().map(|partition| fetch_partition(endpoints, partition))).await.into_iter().flatten().collect();
+    fetched.sort_by_key(|(i, _)| *i);
+    progress::cycle_done(wallets.len(), fetched.len());
+
+    Ok(fetched.into_iter().map(|(_, balance)| balance).collect())
+}
@@ -0,0 +1,258 @@
+// src/fetch_strategy.rs
+// Способ запроса балансов: getBalance на каждый кошелек, getMultipleAccounts пачками или JSON-RPC batch.
+// Без --strategy выбирается по числу кошельков, возможностям эндпоинта и ограничениям из конфигурации
+use anyhow::Result;
+use futures::future::join_all;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::progress;
+use crate::rpc;
+use crate::{get_single_balance, WalletBalance};
+
+// Больше адресов в одном getMultipleAccounts узлы Solana не принимают
+const MAX_ACCOUNTS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    PerWallet,
+    MultipleAccounts,
+    Batch,
+}
+
+impl Strategy {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "per-wallet" => Ok(Strategy::PerWallet),
+            "multiple-accounts" => Ok(Strategy::MultipleAccounts),
+            "batch" => Ok(Strategy::Batch),
+            other => anyhow::bail!("Unknown fetch strategy: {} (expected per-wallet, multiple-accounts, batch)", other),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::PerWallet => "per-wallet",
+            Strategy::MultipleAccounts => "multiple-accounts",
+            Strategy::Batch => "batch",
+        }
+    }
+}
+
+// Секция fetch_strategy конфигурации
+#[derive(Debug, Deserialize)]
+pub struct FetchStrategyConfig {
+    // До этого числа кошельков отдельные getBalance не медленнее пачек
+    #[serde(default = "default_per_wallet_up_to")]
+    pub per_wallet_up_to: usize,
+    // Адресов в одном getMultipleAccounts (не больше 100)
+    #[serde(default = "default_max_accounts_per_request")]
+    pub max_accounts_per_request: usize,
+    // Вызовов в одном JSON-RPC batch; 0 - не использовать batch (провайдер не принимает или считает дороже)
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+}
+
+impl Default for FetchStrategyConfig {
+    fn default() -> Self {
+        FetchStrategyConfig {
+            per_wallet_up_to: default_per_wallet_up_to(),
+            max_accounts_per_request: default_max_accounts_per_request(),
+            max_batch_size: default_max_batch_size(),
+        }
+    }
+}
+
+fn default_per_wallet_up_to() -> usize {
+    10
+}
+
+fn default_max_accounts_per_request() -> usize {
+    MAX_ACCOUNTS
+}
+
+fn default_max_batch_size() -> usize {
+    50
+}
+
+impl FetchStrategyConfig {
+    fn chunk_size(&self, strategy: Strategy) -> usize {
+        match strategy {
+            Strategy::PerWallet => 1,
+            Strategy::MultipleAccounts => self.max_accounts_per_request.clamp(1, MAX_ACCOUNTS),
+            Strategy::Batch => self.max_batch_size.max(1),
+        }
+    }
+}
+
+// Выбранный способ и объяснение выбора для --verbose
+pub struct Selection {
+    pub strategy: Strategy,
+    pub reason: String,
+}
+
+// Число запросов при запросе wallets кошельков пачками по size
+fn requests(wallets: usize, size: usize) -> usize {
+    wallets.div_ceil(size)
+}
+
+// Выбор способа; forced - значение --strategy, grouped - заданы fetch_groups
+pub async fn select(
+    forced: Option<Strategy>,
+    config: &FetchStrategyConfig,
+    rpc_url: &str,
+    wallets: &[String],
+    grouped: bool,
+) -> Result<Selection> {
+    if let Some(strategy) = forced {
+        if strategy == Strategy::Batch && config.max_batch_size == 0 {
+            anyhow::bail!("--strategy batch conflicts with fetch_strategy.max_batch_size: 0");
+        }
+        let mut reason = "set by --strategy".to_string();
+        if grouped && strategy != Strategy::PerWallet {
+            reason.push_str("; fetch_groups limits are not applied");
+        }
+        return Ok(Selection { strategy, reason });
+    }
+
+    let count = wallets.len();
+    if count == 0 || count <= config.per_wallet_up_to {
+        return Ok(Selection {
+            strategy: Strategy::PerWallet,
+            reason: format!("{} wallets, up to {} (per_wallet_up_to) are fetched with one getBalance each", count, config.per_wallet_up_to),
+        });
+    }
+    if grouped {
+        return Ok(Selection {
+            strategy: Strategy::PerWallet,
+            reason: "fetch_groups set concurrency and retry budgets for individual getBalance calls".to_string(),
+        });
+    }
+
+    // Поддержка проверяется пробным запросом одного кошелька: некоторые провайдеры закрывают эти методы
+    let size = config.chunk_size(Strategy::MultipleAccounts);
+    let multiple_accounts = match get_multiple_accounts(rpc_url, &wallets[..1]).await {
+        Ok(_) => {
+            return Ok(Selection {
+                strategy: Strategy::MultipleAccounts,
+                reason: format!(
+                    "{} wallets: {} getMultipleAccounts requests of up to {} addresses instead of {} getBalance calls",
+                    count, requests(count, size), size, count
+                ),
+            });
+        }
+        Err(e) => format!("getMultipleAccounts unavailable ({:#})", e),
+    };
+
+    if config.max_batch_size == 0 {
+        return Ok(Selection {
+            strategy: Strategy::PerWallet,
+            reason: format!("{}, JSON-RPC batch disabled by max_batch_size: 0", multiple_accounts),
+        });
+    }
+    let size = config.chunk_size(Strategy::Batch);
+    match get_batch(rpc_url, &wallets[..count.min(2)]).await {
+        Ok(_) => Ok(Selection {
+            strategy: Strategy::Batch,
+            reason: format!(
+                "{}; {} JSON-RPC batch requests of up to {} getBalance calls instead of {} requests",
+                multiple_accounts, requests(count, size), size, count
+            ),
+        }),
+        Err(e) => Ok(Selection {
+            strategy: Strategy::PerWallet,
+            reason: format!("{}, JSON-RPC batch unavailable ({:#})", multiple_accounts, e),
+        }),
+    }
+}
+
+// Баланс несуществующего аккаунта - 0, как и в ответе getBalance
+#[derive(Debug, Deserialize)]
+struct AccountLamports {
+    lamports: u64,
+}
+
+// Балансы пачки адресов одним getMultipleAccounts; данные аккаунтов не запрашиваются
+async fn get_multiple_accounts(rpc_url: &str, wallets: &[String]) -> Result<Vec<Result<WalletBalance>>> {
+    let params = json!([wallets, { "encoding": "base64", "dataSlice": { "offset": 0, "length": 0 } }]);
+    let accounts: rpc::WithContext<Vec<Option<AccountLamports>>> =
+        rpc::call_with_context(rpc_url, "getMultipleAccounts", params).await?;
+    if accounts.value.len() != wallets.len() {
+        anyhow::bail!("getMultipleAccounts returned {} accounts for {} addresses", accounts.value.len(), wallets.len());
+    }
+
+    Ok(wallets
+        .iter()
+        .zip(accounts.value)
+        .map(|(wallet, account)| {
+            Ok(WalletBalance {
+                address: wallet.clone(),
+                lamports: account.map_or(0, |account| account.lamports),
+                slot: accounts.slot,
+            })
+        })
+        .collect())
+}
+
+// Балансы пачки адресов вызовами getBalance в одном JSON-RPC batch
+async fn get_batch(rpc_url: &str, wallets: &[String]) -> Result<Vec<Result<WalletBalance>>> {
+    let params = wallets.iter().map(|wallet| json!([wallet])).collect();
+    let results = rpc::call_batch(rpc_url, "getBalance", params).await?;
+
+    Ok(wallets
+        .iter()
+        .zip(results)
+        .map(|(wallet, result)| {
+            let balance: rpc::WithContext<u64> = rpc::parse_with_context("getBalance", result?)?;
+            Ok(WalletBalance { address: wallet.clone(), lamports: balance.value, slot: balance.slot })
+        })
+        .collect())
+}
+
+// Пачка целиком; если запрос не прошел, ее кошельки запрашиваются по одному
+async fn fetch_chunk(strategy: Strategy, rpc_url: &str, wallets: &[String]) -> Vec<Result<WalletBalance>> {
+    let fetched = match strategy {
+        Strategy::MultipleAccounts => get_multiple_accounts(rpc_url, wallets).await,
+        Strategy::Batch => get_batch(rpc_url, wallets).await,
+        Strategy::PerWallet => Err(anyhow::anyhow!("not a batched strategy")),
+    };
+    match fetched {
+        Ok(results) => results,
+        Err(e) => {
+            println!("{} request for {} wallets failed, fetching them one by one: {:#}", strategy.name(), wallets.len(), e);
+            join_all(wallets.iter().map(|wallet| get_single_balance(rpc_url, wallet))).await
+        }
+    }
+}
+
+// Балансы пачками; пачки по очереди распределяются по эндпоинтам и запрашиваются одновременно
+pub async fn get_wallet_balances(
+    strategy: Strategy,
+    config: &FetchStrategyConfig,
+    endpoints: &[String],
+    wallets: &[String],
+) -> Vec<WalletBalance> {
+    let chunks: Vec<&[String]> = wallets.chunks(config.chunk_size(strategy)).collect();
+    let results = join_all(
+        chunks.iter().enumerate().map(|(i, chunk)| fetch_chunk(strategy, &endpoints[i % endpoints.len()], chunk)),
+    )
+    .await;
+
+    let mut balances = Vec::new();
+    for (wallet, result) in wallets.iter().zip(results.into_iter().flatten()) {
+        match result {
+            Ok(balance) => {
+                progress::wallet_done(&balance.address, balance.lamports, balance.slot);
+                balances.push(balance);
+            }
+            Err(e) => {
+                println!("Error fetching balance for wallet {}: {:#}", wallet, e);
+                progress::error(Some(wallet), &e);
+            }
+        }
+    }
+    progress::cycle_done(wallets.len(), balances.len());
+
+    balances
+}
+
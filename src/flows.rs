@@ -0,0 +1,112 @@
+// src/flows.rs
+// Переводы SOL между адресами по истории транзакций отслеживаемых кошельков
+use anyhow::{Context as AnyhowContext, Result};
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::tx;
+
+// Перевод SOL внутри транзакции
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    pub from: String,
+    pub to: String,
+    pub lamports: u64,
+}
+
+// Все адреса транзакции: ключи сообщения и адреса из таблиц поиска (v0)
+pub fn account_keys(transaction: &Value) -> Result<Vec<String>> {
+    let static_keys = transaction.pointer("/transaction/message/accountKeys")
+        .and_then(Value::as_array)
+        .context("Transaction has no account keys")?;
+    let loaded = ["writable", "readonly"]
+        .iter()
+        .filter_map(|kind| transaction.pointer(&format!("/meta/loadedAddresses/{}", kind))?.as_array())
+        .flatten();
+
+    Ok(static_keys.iter().chain(loaded).filter_map(Value::as_str).map(str::to_string).collect())
+}
+
+// Переводы SOL в транзакции: списания распределяются по зачислениям в порядке аккаунтов.
+// Комиссия не считается переводом и возвращается к списанию плательщика
+pub fn transfers(transaction: &Value) -> Result<Vec<Transfer>> {
+    let keys = account_keys(transaction)?;
+    let balances = |field: &str| -> Result<Vec<u64>> {
+        transaction.pointer(&format!("/meta/{}", field))
+            .and_then(Value::as_array)
+            .with_context(|| format!("Transaction meta has no {}", field))?
+            .iter()
+            .map(|balance| balance.as_u64().context("Invalid balance"))
+            .collect()
+    };
+    let (pre, post) = (balances("preBalances")?, balances("postBalances")?);
+    let fee = transaction.pointer("/meta/fee").and_then(Value::as_u64).unwrap_or(0);
+
+    let mut senders = Vec::new();
+    let mut receivers = Vec::new();
+    for (index, key) in keys.iter().enumerate() {
+        let (Some(&pre), Some(&post)) = (pre.get(index), post.get(index)) else {
+            continue;
+        };
+        // Первый ключ - плательщик комиссии
+        let delta = post as i128 - pre as i128 + if index == 0 { fee as i128 } else { 0 };
+        if delta < 0 {
+            senders.push((key, (-delta) as u64));
+        } else if delta > 0 {
+            receivers.push((key, delta as u64));
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut receivers = receivers.into_iter();
+    let mut remaining_in = 0;
+    let mut current_to = None;
+    for (from, mut remaining_out) in senders {
+        while remaining_out > 0 {
+            if remaining_in == 0 {
+                let Some((to, amount)) = receivers.next() else {
+                    break;
+                };
+                current_to = Some(to);
+                remaining_in = amount;
+            }
+            let lamports = remaining_out.min(remaining_in);
+            remaining_out -= lamports;
+            remaining_in -= lamports;
+            result.push(Transfer {
+                from: from.clone(),
+                to: current_to.cloned().unwrap_or_default(),
+                lamports,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+// Переводы с участием кошельков по последним limit транзакциям каждого; транзакция учитывается один раз
+pub async fn collect(rpc_url: &str, wallets: &[String], limit: usize) -> Result<Vec<Transfer>> {
+    let tracked: HashSet<&String> = wallets.iter().collect();
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+
+    for wallet in wallets {
+        let signatures = tx::get_signatures(rpc_url, wallet, limit).await?;
+        let fresh: Vec<&tx::SignatureInfo> = signatures
+            .iter()
+            .filter(|info| info.err.is_none() && seen.insert(info.signature.clone()))
+            .collect();
+
+        for (info, transaction) in tx::get_transactions(rpc_url, fresh).await {
+            let parsed = transaction.and_then(|transaction| transfers(&transaction));
+            match parsed {
+                Ok(transfers) => result.extend(
+                    transfers.into_iter().filter(|transfer| tracked.contains(&transfer.from) || tracked.contains(&transfer.to)),
+                ),
+                Err(e) => println!("Skipping transaction {}: {:#}", info.signature, e),
+            }
+        }
+    }
+
+    Ok(result)
+}
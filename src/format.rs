@@ -0,0 +1,315 @@
+// src/format.rs
+// Форматирование сумм и времени для вывода человеку с учетом локали и часового пояса
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::time_zone::TimeZone;
+
+// Разделители разрядов и дробной части
+#[derive(Debug, Clone, Copy)]
+pub struct NumberLocale {
+    pub thousands: Option<char>,
+    pub decimal: char,
+}
+
+// Без локали выводим как раньше: без разделителя разрядов, с точкой
+const PLAIN: NumberLocale = NumberLocale { thousands: None, decimal: '.' };
+
+// Локаль вывода выбирается один раз при запуске
+static LOCALE: OnceLock<NumberLocale> = OnceLock::new();
+
+// Часовой пояс вывода времени; без него время выводится в UTC
+static TIME_ZONE: OnceLock<TimeZone> = OnceLock::new();
+
+// Правила округления для текущего отчета
+static RULES: OnceLock<DisplayRules> = OnceLock::new();
+
+// Больше знаков после запятой не выводится: 10^(знаки) должно помещаться в расчеты округления
+pub const MAX_DECIMALS: u32 = 18;
+
+// Правила отображения сумм
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DisplayRules {
+    // Фиксированное число знаков после запятой для SOL (с округлением)
+    pub sol_decimals: Option<u32>,
+    // Начиная с этой суммы в SOL выводить с суффиксами k/M/B
+    pub compact_from: Option<u64>,
+    // Знаков после запятой для сумм в долларах (по умолчанию 2, до центов)
+    pub usd_decimals: Option<u32>,
+    // Начиная с этой суммы в долларах выводить с суффиксами k/M/B
+    pub usd_compact_from: Option<u64>,
+    // Что скрывать в выводе, которым делятся с партнерами
+    pub redact: Option<Vec<Redact>>,
+}
+
+// Отчеты, весь вывод которых проходит через address/balance; остальные команды скрытие не поддерживают
+pub const REDACTING_REPORTS: [&str; 4] = ["balances", "status-page", "graph", "preflight"];
+
+// Скрываемые при передаче наружу данные
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Redact {
+    // Адреса сокращаются до первых и последних символов
+    Addresses,
+    // Точные суммы заменяются диапазоном по порядку величины
+    Balances,
+    // Метки кошельков не выводятся
+    Labels,
+}
+
+impl Redact {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "addresses" => Ok(Redact::Addresses),
+            "balances" => Ok(Redact::Balances),
+            "labels" => Ok(Redact::Labels),
+            _ => anyhow::bail!("Unknown redaction: {} (expected addresses, balances or labels)", name),
+        }
+    }
+}
+
+// Секция display конфигурации: общие правила и переопределения по отчетам
+#[derive(Debug, Default, Deserialize)]
+pub struct DisplayConfig {
+    #[serde(flatten)]
+    pub default: DisplayRules,
+    // Ключ - имя команды (balances, rent-report, cleanup, ...)
+    #[serde(default)]
+    pub reports: HashMap<String, DisplayRules>,
+}
+
+impl DisplayConfig {
+    // Правила отчета поверх общих правил; число знаков ограничивается MAX_DECIMALS
+    pub fn rules_for(&self, report: &str) -> DisplayRules {
+        let specific = self.reports.get(report).cloned().unwrap_or_default();
+        DisplayRules {
+            sol_decimals: specific.sol_decimals.or(self.default.sol_decimals).map(|shown| shown.min(MAX_DECIMALS)),
+            compact_from: specific.compact_from.or(self.default.compact_from),
+            usd_decimals: specific.usd_decimals.or(self.default.usd_decimals).map(|shown| shown.min(MAX_DECIMALS)),
+            usd_compact_from: specific.usd_compact_from.or(self.default.usd_compact_from),
+            redact: specific.redact.or_else(|| self.default.redact.clone()),
+        }
+    }
+}
+
+impl NumberLocale {
+    // Разбор тега вида "de", "de-DE", "fr_FR"; "C" и "plain" отключают локализацию
+    pub fn parse(tag: &str) -> Result<Self> {
+        let tag = tag.replace('_', "-").to_lowercase();
+        let language = tag.split('-').next().unwrap_or_default();
+
+        let locale = match (language, tag.as_str()) {
+            (_, "c" | "plain" | "posix") => PLAIN,
+            (_, "de-ch" | "it-ch") => NumberLocale { thousands: Some('\''), decimal: '.' },
+            ("en" | "ja" | "zh" | "ko" | "he" | "th", _) => NumberLocale { thousands: Some(','), decimal: '.' },
+            ("de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro", _) => {
+                NumberLocale { thousands: Some('.'), decimal: ',' }
+            }
+            // Узкий неразрывный пробел как разделитель разрядов
+            ("fr" | "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "hu" | "bg", _) => {
+                NumberLocale { thousands: Some('\u{202f}'), decimal: ',' }
+            }
+            _ => anyhow::bail!("Unsupported locale: {}", tag),
+        };
+
+        Ok(locale)
+    }
+}
+
+// Установка локали вывода; вызывается один раз из main
+pub fn set_locale(locale: NumberLocale) {
+    let _ = LOCALE.set(locale);
+}
+
+fn locale() -> NumberLocale {
+    LOCALE.get().copied().unwrap_or(PLAIN)
+}
+
+// Установка часового пояса вывода; вызывается один раз из main
+pub fn set_time_zone(zone: TimeZone) {
+    let _ = TIME_ZONE.set(zone);
+}
+
+// Время в поясе вывода со смещением, действующим в этот момент
+fn local(utc: DateTime<Utc>) -> Option<DateTime<FixedOffset>> {
+    let zone = TIME_ZONE.get()?;
+    FixedOffset::east_opt(zone.offset_at(utc.timestamp())).map(|offset| utc.with_timezone(&offset))
+}
+
+// Время события (unix time) в часовом поясе вывода
+pub fn time(timestamp: i64) -> String {
+    let Some(utc) = DateTime::<Utc>::from_timestamp(timestamp, 0) else {
+        return timestamp.to_string();
+    };
+    match (local(utc), TIME_ZONE.get()) {
+        (Some(local), Some(zone)) => format!("{} {}", local.format("%Y-%m-%d %H:%M:%S %:z"), zone.name()),
+        _ => utc.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+    }
+}
+
+// Календарный день события в часовом поясе вывода
+pub fn date(timestamp: i64) -> NaiveDate {
+    let utc = DateTime::<Utc>::from_timestamp(timestamp, 0).unwrap_or_default();
+    local(utc).map_or_else(|| utc.date_naive(), |local| local.date_naive())
+}
+
+// Установка правил отображения для текущего отчета; вызывается один раз из main
+pub fn set_rules(rules: DisplayRules) {
+    let _ = RULES.set(rules);
+}
+
+// Сумма в SOL; без правил - без потери точности и без лишних нулей в дробной части
+pub fn sol(lamports: u64) -> String {
+    let rules = RULES.get().cloned().unwrap_or_default();
+
+    if let Some(from) = rules.compact_from {
+        if lamports / 1_000_000_000 >= from {
+            return compact(lamports, 9, rules.sol_decimals.unwrap_or(2));
+        }
+    }
+    match rules.sol_decimals {
+        Some(shown) => fixed(lamports, 9, shown),
+        None => units(lamports, 9),
+    }
+}
+
+// Скрываются ли в текущем отчете данные what
+pub fn redacts(what: Redact) -> bool {
+    RULES.get().and_then(|rules| rules.redact.as_ref()).is_some_and(|redact| redact.contains(&what))
+}
+
+// Адрес для вывода; при скрытии адресов остаются первые и последние 4 символа
+pub fn address(address: &str) -> String {
+    if !redacts(Redact::Addresses) {
+        return address.to_string();
+    }
+    match (address.get(..4), address.get(address.len().saturating_sub(4)..)) {
+        (Some(head), Some(tail)) if address.len() > 8 => format!("{}...{}", head, tail),
+        _ => "...".to_string(),
+    }
+}
+
+// Баланс в SOL для вывода; при скрытии балансов - диапазон вида 10-100
+pub fn balance(lamports: u64) -> String {
+    if !redacts(Redact::Balances) {
+        return sol(lamports);
+    }
+    let whole = lamports / 1_000_000_000;
+    if lamports == 0 {
+        return "0".to_string();
+    }
+    if whole == 0 {
+        return "<1".to_string();
+    }
+    let low = 10u64.pow(whole.ilog10());
+    format!("{}-{}", group(&low.to_string(), locale()), group(&(low * 10).to_string(), locale()))
+}
+
+// Сумма в SOL со знаком
+pub fn signed_sol(lamports: i64) -> String {
+    let sign = if lamports < 0 { "-" } else { "+" };
+    format!("{}{}", sign, sol(lamports.unsigned_abs()))
+}
+
+// Сумма в долларах; без правил - с округлением до центов
+pub fn usd(amount: f64) -> String {
+    let rules = RULES.get().cloned().unwrap_or_default();
+    let sign = if amount < 0.0 { "-" } else { "" };
+    let cents = (amount.abs() * 100.0).round() as u64;
+    let shown = rules.usd_decimals.unwrap_or(2);
+
+    if let Some(from) = rules.usd_compact_from {
+        if cents / 100 >= from {
+            return format!("{}${}", sign, compact(cents, 2, shown));
+        }
+    }
+    format!("{}${}", sign, fixed(cents, 2, shown))
+}
+
+// Сокращенная запись с суффиксом: 1,23M
+fn compact(amount: u64, decimals: u32, shown: u32) -> String {
+    let whole = amount / 10u64.pow(decimals);
+    let (exponent, suffix) = match whole {
+        w if w >= 1_000_000_000 => (9, "B"),
+        w if w >= 1_000_000 => (6, "M"),
+        w if w >= 1_000 => (3, "k"),
+        _ => (0, ""),
+    };
+    format!("{}{}", fixed(amount, decimals + exponent, shown), suffix)
+}
+
+// Сумма, округленная до заданного числа знаков после запятой
+fn fixed(amount: u64, decimals: u32, shown: u32) -> String {
+    let amount = amount as u128;
+    let rounded = if shown < decimals {
+        let factor = 10u128.pow(decimals - shown);
+        (amount + factor / 2) / factor
+    } else {
+        amount * 10u128.pow(shown - decimals)
+    };
+
+    let divisor = 10u128.pow(shown);
+    let whole = group(&(rounded / divisor).to_string(), locale());
+    if shown == 0 {
+        return whole;
+    }
+    format!("{}{}{:0width$}", whole, locale().decimal, rounded % divisor, width = shown as usize)
+}
+
+// Сумма в минимальных единицах с заданным числом знаков; при непредставимом числе знаков - как есть
+pub fn units(amount: u64, decimals: u32) -> String {
+    let Some(divisor) = 10u64.checked_pow(decimals) else {
+        return group(&amount.to_string(), locale());
+    };
+    let whole = group(&(amount / divisor).to_string(), locale());
+
+    let fraction = format!("{:0width$}", amount % divisor, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole
+    } else {
+        format!("{}{}{}", whole, locale().decimal, fraction)
+    }
+}
+
+// Разбиение целой части на группы по три цифры
+fn group(digits: &str, locale: NumberLocale) -> String {
+    let Some(separator) = locale.thousands else {
+        return digits.to_string();
+    };
+
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units_with_unrepresentable_decimals() {
+        assert_eq!(units(1_500, 3), "1.5");
+        assert_eq!(units(u64::MAX, 19), "1.8446744073709551615");
+        assert_eq!(units(12_345, 20), "12345");
+    }
+
+    #[test]
+    fn decimals_are_limited() {
+        let config: DisplayConfig = serde_yaml::from_str("sol_decimals: 40\nreports: { fees: { usd_decimals: 25 } }").unwrap();
+        let rules = config.rules_for("fees");
+        assert_eq!(rules.sol_decimals, Some(MAX_DECIMALS));
+        assert_eq!(rules.usd_decimals, Some(MAX_DECIMALS));
+
+        assert_eq!(fixed(u64::MAX, 9, MAX_DECIMALS), "18446744073.709551615000000000");
+        assert_eq!(compact(u64::MAX, 9, MAX_DECIMALS), "18.446744073709551615B");
+    }
+}
@@ -0,0 +1,97 @@
+// src/graph.rs
+// Граф переводов между отслеживаемыми кошельками и контрагентами для Graphviz / Mermaid
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+use crate::flows::{self, Transfer};
+use crate::format;
+
+// Формат выгрузки
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    Dot,
+    Mermaid,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "dot" => Ok(Format::Dot),
+            "mermaid" => Ok(Format::Mermaid),
+            other => anyhow::bail!("Unknown graph format: {} (expected dot, mermaid)", other),
+        }
+    }
+}
+
+// Ребро графа: сумма и число переводов от from к to
+#[derive(Debug, Default)]
+struct Edge {
+    lamports: u64,
+    count: usize,
+}
+
+// Граф по последним limit транзакциям каждого кошелька; выводится в stdout
+pub async fn export(rpc_url: &str, wallets: &[String], limit: usize, format: Format) -> Result<()> {
+    let transfers = flows::collect(rpc_url, wallets, limit).await?;
+    print!("{}", render(wallets, &transfers, format));
+    Ok(())
+}
+
+fn render(wallets: &[String], transfers: &[Transfer], format: Format) -> String {
+    let tracked: HashSet<&String> = wallets.iter().collect();
+    let mut edges: BTreeMap<(&String, &String), Edge> = BTreeMap::new();
+    for transfer in transfers {
+        let edge = edges.entry((&transfer.from, &transfer.to)).or_default();
+        edge.lamports += transfer.lamports;
+        edge.count += 1;
+    }
+
+    // Отслеживаемые кошельки выводятся всегда, контрагенты - только со связями
+    let mut nodes: Vec<&String> = wallets.iter().collect();
+    for (from, to) in edges.keys() {
+        for address in [*from, *to] {
+            if !nodes.contains(&address) {
+                nodes.push(address);
+            }
+        }
+    }
+    let id = |address: &String| nodes.iter().position(|node| *node == address).unwrap_or_default();
+    let edge_label = |edge: &Edge| format!("{} SOL ({})", format::balance(edge.lamports), edge.count);
+
+    let mut out = String::new();
+    match format {
+        Format::Dot => {
+            let _ = writeln!(out, "digraph flows {{");
+            let _ = writeln!(out, "  rankdir=LR;");
+            for (i, node) in nodes.iter().enumerate() {
+                let style = if tracked.contains(node) { ", style=filled, fillcolor=lightblue" } else { "" };
+                let _ = writeln!(out, "  n{} [label=\"{}\", tooltip=\"{}\"{}];", i, short(node), format::address(node), style);
+            }
+            for ((from, to), edge) in &edges {
+                let _ = writeln!(out, "  n{} -> n{} [label=\"{}\"];", id(from), id(to), edge_label(edge));
+            }
+            let _ = writeln!(out, "}}");
+        }
+        Format::Mermaid => {
+            let _ = writeln!(out, "flowchart LR");
+            for (i, node) in nodes.iter().enumerate() {
+                let class = if tracked.contains(node) { ":::tracked" } else { "" };
+                let _ = writeln!(out, "  n{}[\"{}\"]{}", i, short(node), class);
+            }
+            for ((from, to), edge) in &edges {
+                let _ = writeln!(out, "  n{} -->|\"{}\"| n{}", id(from), edge_label(edge), id(to));
+            }
+            let _ = writeln!(out, "  classDef tracked fill:#add8e6");
+        }
+    }
+    out
+}
+
+// Сокращенный адрес для подписи узла
+fn short(address: &str) -> String {
+    match (address.get(..4), address.get(address.len().saturating_sub(4)..)) {
+        (Some(head), Some(tail)) if address.len() > 8 => format!("{}..{}", head, tail),
+        _ => address.to_string(),
+    }
+}
@@ -0,0 +1,134 @@
+// src/groups.rs
+// Именованные группы кошельков и операции над множествами адресов
+use anyhow::{Context as AnyhowContext, Result};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+// Имя встроенной группы со списком wallets из конфигурации
+pub const ALL_WALLETS: &str = "wallets";
+
+// Элемент секции groups: список адресов или выражение над другими группами
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum GroupConfig {
+    Addresses(Vec<String>),
+    Derived(SetExpr),
+}
+
+// Операция над группами; порядок адресов берется из первой группы
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetOp {
+    Union,
+    Intersect,
+    Except,
+}
+
+impl SetOp {
+    pub fn name(self) -> &'static str {
+        match self {
+            SetOp::Union => "union",
+            SetOp::Intersect => "intersect",
+            SetOp::Except => "except",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SetExpr {
+    Union(Vec<String>),
+    Intersect(Vec<String>),
+    Except(Vec<String>),
+}
+
+impl SetExpr {
+    fn parts(&self) -> (SetOp, &[String]) {
+        match self {
+            SetExpr::Union(groups) => (SetOp::Union, groups),
+            SetExpr::Intersect(groups) => (SetOp::Intersect, groups),
+            SetExpr::Except(groups) => (SetOp::Except, groups),
+        }
+    }
+}
+
+// Применение операции к спискам адресов без повторов
+pub fn apply(op: SetOp, sets: &[Vec<String>]) -> Vec<String> {
+    let Some((first, rest)) = sets.split_first() else {
+        return Vec::new();
+    };
+    let rest: Vec<HashSet<&String>> = rest.iter().map(|set| set.iter().collect()).collect();
+    let mut seen = HashSet::new();
+
+    let candidates: Box<dyn Iterator<Item = &String>> = match op {
+        SetOp::Union => Box::new(sets.iter().flatten()),
+        _ => Box::new(first.iter()),
+    };
+    candidates
+        .filter(|address| match op {
+            SetOp::Union => true,
+            SetOp::Intersect => rest.iter().all(|set| set.contains(address)),
+            SetOp::Except => !rest.iter().any(|set| set.contains(address)),
+        })
+        .filter(|address| seen.insert(*address))
+        .cloned()
+        .collect()
+}
+
+// Разрешение групп конфигурации в списки адресов
+pub struct Groups<'a> {
+    wallets: &'a [String],
+    configs: &'a HashMap<String, GroupConfig>,
+}
+
+impl<'a> Groups<'a> {
+    pub fn new(wallets: &'a [String], configs: &'a HashMap<String, GroupConfig>) -> Self {
+        Groups { wallets, configs }
+    }
+
+    // Адреса группы; производные группы вычисляются рекурсивно
+    pub fn resolve(&self, name: &str) -> Result<Vec<String>> {
+        self.resolve_from(name, &mut Vec::new())
+    }
+
+    fn resolve_from(&self, name: &str, path: &mut Vec<String>) -> Result<Vec<String>> {
+        if name == ALL_WALLETS && !self.configs.contains_key(name) {
+            return Ok(self.wallets.to_vec());
+        }
+        if path.iter().any(|visited| visited == name) {
+            anyhow::bail!("Group cycle: {} -> {}", path.join(" -> "), name);
+        }
+        let config = self.configs.get(name).with_context(|| format!("Unknown group: {}", name))?;
+
+        match config {
+            GroupConfig::Addresses(addresses) => Ok(apply(SetOp::Union, std::slice::from_ref(addresses))),
+            GroupConfig::Derived(expr) => {
+                let (op, groups) = expr.parts();
+                path.push(name.to_string());
+                let sets = groups
+                    .iter()
+                    .map(|group| self.resolve_from(group, path))
+                    .collect::<Result<Vec<_>>>()?;
+                path.pop();
+                Ok(apply(op, &sets))
+            }
+        }
+    }
+
+    // Имена всех групп, включая встроенную
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.configs.keys().map(String::as_str).collect();
+        if !self.configs.contains_key(ALL_WALLETS) {
+            names.push(ALL_WALLETS);
+        }
+        names.sort_unstable();
+        names
+    }
+}
+
+// Вывод результата операции над двумя группами или одной группы
+pub fn print_addresses(title: &str, addresses: &[String]) {
+    println!("{}: {} addresses", title, addresses.len());
+    for address in addresses {
+        println!("{}", address);
+    }
+}
@@ -0,0 +1,87 @@
+// src/heartbeat.rs
+// Ожидаемая активность кошельков: [ALERT], если кошелек дольше заданного не совершал транзакций
+use anyhow::Result;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::flows;
+use crate::format;
+use crate::tx;
+
+// Сколько последних подписей просматривается в поиске подходящей транзакции
+const SCAN_LIMIT: usize = 50;
+
+// Элемент секции heartbeats
+#[derive(Debug, Deserialize)]
+pub struct Heartbeat {
+    pub wallet: String,
+    pub label: Option<String>,
+    // Максимальный перерыв между успешными транзакциями
+    pub max_silence_minutes: i64,
+    // Считать только транзакции, оплаченные кошельком (иначе и входящие переводы)
+    #[serde(default)]
+    pub as_fee_payer: bool,
+}
+
+// Время последней успешной транзакции кошелька среди последних SCAN_LIMIT
+async fn last_activity(rpc_url: &str, heartbeat: &Heartbeat) -> Result<Option<i64>> {
+    let signatures = tx::get_signatures(rpc_url, &heartbeat.wallet, SCAN_LIMIT).await?;
+    let successful: Vec<&tx::SignatureInfo> = signatures.iter().filter(|info| info.err.is_none()).collect();
+    if !heartbeat.as_fee_payer {
+        return Ok(successful.first().and_then(|info| info.block_time));
+    }
+
+    for (info, transaction) in tx::get_transactions(rpc_url, successful).await {
+        let payer = match transaction.and_then(|transaction| flows::account_keys(&transaction)) {
+            Ok(keys) => keys.into_iter().next(),
+            Err(e) => {
+                println!("Skipping transaction {}: {:#}", info.signature, e);
+                continue;
+            }
+        };
+        if payer.as_ref() == Some(&heartbeat.wallet) {
+            return Ok(info.block_time);
+        }
+    }
+    Ok(None)
+}
+
+// Проверка всех ожиданий; запускается по расписанию чаще самого короткого max_silence_minutes
+pub async fn run_report(rpc_url: &str, heartbeats: &[Heartbeat]) -> Result<()> {
+    if heartbeats.is_empty() {
+        anyhow::bail!("Config has no heartbeats");
+    }
+    let now = Utc::now().timestamp();
+    let mut alerts = 0;
+
+    for heartbeat in heartbeats {
+        let name = heartbeat.label.as_deref().unwrap_or(&heartbeat.wallet);
+        let kind = if heartbeat.as_fee_payer { "paid transaction" } else { "transaction" };
+        match last_activity(rpc_url, heartbeat).await {
+            Ok(Some(time)) if now - time <= heartbeat.max_silence_minutes * 60 => {
+                println!("[OK] {}: last {} at {}", name, kind, format::time(time));
+            }
+            Ok(Some(time)) => {
+                alerts += 1;
+                println!(
+                    "[ALERT] {}: no {} for {} minutes (expected at least every {}), last at {}",
+                    name, kind, (now - time) / 60, heartbeat.max_silence_minutes, format::time(time)
+                );
+            }
+            Ok(None) => {
+                alerts += 1;
+                println!("[ALERT] {}: no successful {} among the last {} signatures", name, kind, SCAN_LIMIT);
+            }
+            Err(e) => {
+                alerts += 1;
+                println!("[ALERT] {}: activity check failed: {:#}", name, e);
+            }
+        }
+    }
+
+    if alerts > 0 {
+        anyhow::bail!("{} of {} wallets missed their heartbeat", alerts, heartbeats.len());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// src/history.rs
+// Вывод последних транзакций адреса с memo и фильтрацией по нему
+use anyhow::Result;
+use std::collections::HashSet;
+
+use crate::flows;
+use crate::format;
+use crate::lookalike::looks_alike;
+use crate::tx;
+
+// Переводы меньше этой суммы считаются "пылью" (0.001 SOL)
+const DUST_LAMPORTS: u64 = 1_000_000;
+
+// Параметры вывода истории
+pub struct HistoryOptions<'a> {
+    pub limit: usize,
+    pub memo_filter: Option<&'a str>,
+    // Поиск "отравления адресов"; требует загрузки каждой транзакции
+    pub check_poisoning: bool,
+    pub hide_poisoned: bool,
+    // Заведомо известные контрагенты (отслеживаемые кошельки)
+    pub known: &'a [String],
+}
+
+// Вывод истории транзакций адреса, при необходимости только с подходящим memo
+pub async fn print_history(rpc_url: &str, address: &str, options: &HistoryOptions<'_>) -> Result<()> {
+    let signatures = tx::get_signatures(rpc_url, address, options.limit).await?;
+
+    // Поиск по memo без учета регистра
+    let filter = options.memo_filter.map(str::to_lowercase);
+    let matching: Vec<&tx::SignatureInfo> = signatures
+        .iter()
+        .filter(|info| match &filter {
+            Some(filter) => info.memos().iter().any(|memo| memo.to_lowercase().contains(filter)),
+            None => true,
+        })
+        .collect();
+
+    let poisoned = if options.check_poisoning || options.hide_poisoned {
+        detect_poisoning(rpc_url, address, &signatures, options.known).await
+    } else {
+        Vec::new()
+    };
+    let poisoned_signatures: HashSet<&str> = poisoned.iter().map(|p| p.signature.as_str()).collect();
+
+    let shown: Vec<&tx::SignatureInfo> = matching
+        .into_iter()
+        .filter(|info| !(options.hide_poisoned && poisoned_signatures.contains(info.signature.as_str())))
+        .collect();
+
+    println!("History for {} ({} transactions):", address, shown.len());
+    for info in shown {
+        let time = info.block_time.map_or_else(|| "unknown time".to_string(), format::time);
+        let status = if info.err.is_some() { "failed" } else { "ok" };
+        let flag = if poisoned_signatures.contains(info.signature.as_str()) { "  [POISONING?]" } else { "" };
+
+        let memos = info.memos();
+        if memos.is_empty() {
+            println!("{}  slot {}  {}  {}{}", info.signature, info.slot, time, status, flag);
+        } else {
+            println!("{}  slot {}  {}  {}  memo: {}{}", info.signature, info.slot, time, status, memos.join(" | "), flag);
+        }
+    }
+
+    if options.check_poisoning || options.hide_poisoned {
+        println!("Security: {} possible address poisoning transfers", poisoned.len());
+        for entry in &poisoned {
+            println!(
+                "  {} from {} ({} lamports) imitates {}",
+                entry.signature, entry.sender, entry.lamports, entry.imitates
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// Подозрительный перевод-пыль с адреса, похожего на известного контрагента
+struct PoisoningEntry {
+    signature: String,
+    sender: String,
+    lamports: u64,
+    imitates: String,
+}
+
+// Поиск переводов-пыли с адресов, совпадающих с контрагентами по началу и концу
+async fn detect_poisoning(
+    rpc_url: &str,
+    address: &str,
+    signatures: &[tx::SignatureInfo],
+    known: &[String],
+) -> Vec<PoisoningEntry> {
+    let successful: Vec<&tx::SignatureInfo> = signatures.iter().filter(|info| info.err.is_none()).collect();
+
+    let mut transactions = Vec::new();
+    for (info, result) in tx::get_transactions(rpc_url, successful).await {
+        match result {
+            Ok(transaction) => transactions.push((info, transaction)),
+            Err(e) => println!("Skipping {} in poisoning check: {:#}", info.signature, e),
+        }
+    }
+
+    // Контрагенты: отслеживаемые кошельки, получатели исходящих и отправители заметных входящих
+    let mut counterparties: HashSet<String> = known.iter().cloned().collect();
+    let mut incoming_dust = Vec::new();
+    for (info, transaction) in &transactions {
+        let keys = flows::account_keys(transaction).unwrap_or_default();
+        let Some(payer) = keys.first() else {
+            continue;
+        };
+
+        if payer == address {
+            counterparties.extend(keys.iter().filter(|key| {
+                *key != address && tx::sol_received(transaction, key).unwrap_or(0) > 0
+            }).cloned());
+        } else {
+            let received = tx::sol_received(transaction, address).unwrap_or(0);
+            if received >= DUST_LAMPORTS {
+                counterparties.insert(payer.clone());
+            } else {
+                incoming_dust.push((info.signature.clone(), payer.clone(), received));
+            }
+        }
+    }
+
+    incoming_dust
+        .into_iter()
+        .filter(|(_, sender, _)| !counterparties.contains(sender))
+        .filter_map(|(signature, sender, lamports)| {
+            let imitates = counterparties.iter().find(|known| looks_alike(&sender, known))?.clone();
+            Some(PoisoningEntry { signature, sender, lamports, imitates })
+        })
+        .collect()
+}
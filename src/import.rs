@@ -0,0 +1,163 @@
+// src/import.rs
+// Импорт адресов кошельков из CSV выгрузок бирж и таблиц в config.yaml
+use anyhow::{Context as AnyhowContext, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::lookalike;
+use crate::transaction;
+
+// Колонки и формат CSV файла
+pub struct CsvOptions<'a> {
+    // Имя колонки из заголовка или номер колонки начиная с 1
+    pub address_column: &'a str,
+    pub label_column: Option<&'a str>,
+    pub delimiter: char,
+    // В файле нет строки заголовка; колонки задаются только номерами
+    pub no_header: bool,
+    // Только показать, что будет добавлено
+    pub dry_run: bool,
+    // Добавлять и адреса, похожие на известные
+    pub force: bool,
+}
+
+// Импорт новых адресов из CSV в список wallets конфигурации; метки пишутся комментариями.
+// known - все адреса конфигурации, с которыми сравниваются новые на опечатки
+pub fn import_csv(
+    file: &Path,
+    config_path: &Path,
+    existing: &[String],
+    known: &[String],
+    options: &CsvOptions,
+) -> Result<()> {
+    let text = fs::read_to_string(file).with_context(|| format!("Failed to read CSV file: {:?}", file))?;
+    let mut rows = text.lines().filter(|line| !line.trim().is_empty()).map(|line| split_row(line, options.delimiter));
+
+    let header = if options.no_header { None } else { rows.next() };
+    let address_index = column_index(options.address_column, header.as_deref())?;
+    let label_index = options.label_column.map(|column| column_index(column, header.as_deref())).transpose()?;
+
+    let mut seen: HashSet<String> = existing.iter().cloned().collect();
+    let (mut added, mut duplicates, mut invalid) = (Vec::new(), 0, 0);
+    for row in rows {
+        let Some(address) = row.get(address_index).map(|value| value.trim()) else {
+            invalid += 1;
+            continue;
+        };
+        // Выгрузки бирж содержат адреса других сетей; берем только адреса Solana
+        if transaction::decode_pubkey(address).is_err() {
+            invalid += 1;
+            continue;
+        }
+        if !seen.insert(address.to_string()) {
+            duplicates += 1;
+            continue;
+        }
+
+        let label = label_index
+            .and_then(|index| row.get(index))
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty());
+        added.push((address.to_string(), label));
+    }
+
+    for (address, label) in &added {
+        match label {
+            Some(label) => println!("+ {}  ({})", address, label),
+            None => println!("+ {}", address),
+        }
+    }
+    println!(
+        "{} new wallets, {} duplicates skipped, {} rows without a valid Solana address",
+        added.len(), duplicates, invalid
+    );
+    let addresses: Vec<&str> = added.iter().map(|(address, _)| address.as_str()).collect();
+    if options.dry_run {
+        lookalike::warn_similar(&addresses, known);
+        return Ok(());
+    }
+    if added.is_empty() {
+        return Ok(());
+    }
+    lookalike::check_new(&addresses, known, options.force)?;
+
+    let config = fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to open config file: {:?}", config_path))?;
+    let updated = append_wallets(&config, &added)?;
+    fs::write(config_path, updated).with_context(|| format!("Failed to write config file: {:?}", config_path))?;
+    println!("Config {:?} updated", config_path);
+
+    Ok(())
+}
+
+// Номер колонки: число (с 1) или имя из заголовка без учета регистра
+fn column_index(column: &str, header: Option<&[String]>) -> Result<usize> {
+    if let Ok(number) = column.parse::<usize>() {
+        if number == 0 {
+            anyhow::bail!("Column numbers start at 1");
+        }
+        return Ok(number - 1);
+    }
+
+    let header = header.with_context(|| format!("Column '{}' can only be found by name in a file with a header", column))?;
+    header
+        .iter()
+        .position(|name| name.trim().eq_ignore_ascii_case(column))
+        .with_context(|| format!("Column '{}' not found; header has: {}", column, header.join(", ")))
+}
+
+// Разбор строки CSV с учетом кавычек и удвоенных кавычек внутри них
+fn split_row(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+// Добавление адресов в конец блока wallets с сохранением остального текста и комментариев
+pub fn append_wallets(config: &str, added: &[(String, Option<String>)]) -> Result<String> {
+    let lines: Vec<&str> = config.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim_end() == "wallets:")
+        .context("Config has no block-style 'wallets:' list to append to")?;
+
+    // Последний элемент списка; комментарии внутри блока относятся к нему
+    let mut end = start;
+    let mut indent = "  ".to_string();
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("- ") {
+            end = i;
+            indent = line[..line.len() - trimmed.len()].to_string();
+        } else if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            break;
+        }
+    }
+
+    let mut output: Vec<String> = lines[..=end].iter().map(|line| line.to_string()).collect();
+    for (address, label) in added {
+        match label {
+            Some(label) => output.push(format!("{}- \"{}\"  # {}", indent, address, label.replace('\n', " "))),
+            None => output.push(format!("{}- \"{}\"", indent, address)),
+        }
+    }
+    output.extend(lines[end + 1..].iter().map(|line| line.to_string()));
+
+    Ok(output.join("\n") + "\n")
+}
@@ -0,0 +1,168 @@
+// src/init.rs
+// Интерактивное создание стартовой конфигурации
+use anyhow::{Context as AnyhowContext, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::format;
+use crate::transaction;
+use crate::Config;
+
+// Кластеры на выбор: название и публичный эндпоинт
+const CLUSTERS: &[(&str, &str)] = &[
+    ("mainnet-beta", "https://api.mainnet-beta.solana.com"),
+    ("devnet", "https://api.devnet.solana.com"),
+    ("testnet", "https://api.testnet.solana.com"),
+];
+
+// Диалог в терминале и запись конфигурации в path
+pub fn run(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        anyhow::bail!("{:?} already exists; use --force to overwrite it", path);
+    }
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+
+    let rpc_url = ask_cluster(&mut input)?;
+    let wallets = ask_wallets(&mut input)?;
+    let locale = ask_locale(&mut input)?;
+    let sol_decimals = ask_sol_decimals(&mut input)?;
+
+    let text = render(&rpc_url, &wallets, locale.as_deref(), sol_decimals);
+    serde_yaml::from_str::<Config>(&text).context("Generated config does not parse")?;
+    fs::write(path, text).with_context(|| format!("Failed to write config file: {:?}", path))?;
+
+    println!("Config with {} wallets written to {:?}", wallets.len(), path);
+    println!("Check it with: cargo run -- --config {:?} lint", path);
+
+    Ok(())
+}
+
+// Строка ответа без перевода строки; None в конце ввода
+pub fn prompt(input: &mut impl BufRead, question: &str) -> Result<Option<String>> {
+    print!("{}", question);
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+fn ask_cluster(input: &mut impl BufRead) -> Result<String> {
+    println!("Cluster:");
+    for (i, (name, url)) in CLUSTERS.iter().enumerate() {
+        println!("  {}) {} ({})", i + 1, name, url);
+    }
+    println!("  or paste the URL of your RPC provider");
+
+    loop {
+        let answer = prompt(input, "Choice [1]: ")?.context("Input ended before a cluster was chosen")?;
+        if answer.is_empty() {
+            return Ok(CLUSTERS[0].1.to_string());
+        }
+        if answer.starts_with("http://") || answer.starts_with("https://") {
+            return Ok(answer);
+        }
+        match answer.parse::<usize>().ok().and_then(|n| CLUSTERS.get(n.wrapping_sub(1))) {
+            Some((_, url)) => return Ok(url.to_string()),
+            None => println!("Enter 1-{} or an http(s) URL", CLUSTERS.len()),
+        }
+    }
+}
+
+// Адреса по одному в строке или через пробел/запятую; @file - чтение адресов из файла
+fn ask_wallets(input: &mut impl BufRead) -> Result<Vec<String>> {
+    println!("Wallet addresses: paste them (one or more per line) or @path to read a file; empty line to finish");
+
+    let mut wallets = Vec::new();
+    let mut seen = HashSet::new();
+    while let Some(line) = prompt(input, "> ")? {
+        if line.is_empty() {
+            if wallets.is_empty() {
+                println!("At least one wallet is required");
+                continue;
+            }
+            break;
+        }
+
+        let text = match line.strip_prefix('@') {
+            Some(file) => match fs::read_to_string(file.trim()) {
+                Ok(text) => text,
+                Err(e) => {
+                    println!("Cannot read {}: {}", file.trim(), e);
+                    continue;
+                }
+            },
+            None => line,
+        };
+
+        for address in text.split(|c: char| c.is_whitespace() || c == ',').filter(|a| !a.is_empty()) {
+            if let Err(e) = transaction::decode_pubkey(address) {
+                println!("Skipped {}: {:#}", address, e);
+            } else if seen.insert(address.to_string()) {
+                wallets.push(address.to_string());
+            }
+        }
+        println!("{} wallets so far", wallets.len());
+    }
+
+    if wallets.is_empty() {
+        anyhow::bail!("Input ended before any wallet was entered");
+    }
+    Ok(wallets)
+}
+
+fn ask_locale(input: &mut impl BufRead) -> Result<Option<String>> {
+    loop {
+        let Some(answer) = prompt(input, "Number locale (en, de, fr, ...; empty for plain 1234.5): ")? else {
+            return Ok(None);
+        };
+        if answer.is_empty() {
+            return Ok(None);
+        }
+        match format::NumberLocale::parse(&answer) {
+            Ok(_) => return Ok(Some(answer)),
+            Err(e) => println!("{:#}", e),
+        }
+    }
+}
+
+fn ask_sol_decimals(input: &mut impl BufRead) -> Result<Option<u32>> {
+    loop {
+        let Some(answer) = prompt(input, "Round SOL amounts to N decimals (empty for full precision): ")? else {
+            return Ok(None);
+        };
+        if answer.is_empty() {
+            return Ok(None);
+        }
+        match answer.parse::<u32>() {
+            Ok(decimals) if decimals <= 9 => return Ok(Some(decimals)),
+            _ => println!("Enter a number from 0 to 9"),
+        }
+    }
+}
+
+// Текст конфигурации в том же виде, что и config.yaml из репозитория
+fn render(rpc_url: &str, wallets: &[String], locale: Option<&str>, sol_decimals: Option<u32>) -> String {
+    let mut text = String::from("# config.yaml\n# URL Solana RPC эндпоинта\n");
+    text.push_str(&format!("rpc_url: \"{}\"\n\n", rpc_url));
+
+    text.push_str("# Список адресов кошельков для проверки баланса\nwallets:\n");
+    for wallet in wallets {
+        text.push_str(&format!("  - \"{}\"\n", wallet));
+    }
+
+    if let Some(locale) = locale {
+        text.push_str(&format!("\n# Локаль для вывода чисел\nlocale: \"{}\"\n", locale));
+    }
+    if let Some(decimals) = sol_decimals {
+        text.push_str(&format!("\n# Округление сумм\ndisplay:\n  sol_decimals: {}\n", decimals));
+    }
+
+    text
+}
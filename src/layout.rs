@@ -0,0 +1,389 @@
+// src/layout.rs
+// Разбор данных аккаунтов по Borsh раскладке из конфигурации или из описания аккаунта в Anchor IDL
+use anyhow::{Context as AnyhowContext, Result};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::transaction;
+
+// Длина дискриминатора, с которого начинаются аккаунты Anchor
+const ANCHOR_DISCRIMINATOR_LEN: usize = 8;
+
+// Вложенность defined типов IDL, после которой описание считается циклическим
+const MAX_DEFINED_DEPTH: usize = 16;
+
+// Элемент секции account_layouts: поля по порядку или аккаунт из Anchor IDL
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum LayoutConfig {
+    Fields {
+        // Пропускаемые байты в начале данных (8 для дискриминатора Anchor)
+        #[serde(default)]
+        skip: usize,
+        fields: Vec<FieldConfig>,
+    },
+    Idl {
+        idl: PathBuf,
+        account: String,
+    },
+}
+
+// Поле раскладки: тип вида u64, pubkey, string, [u8; 32], vec<u16>, option<pubkey>
+#[derive(Debug, Deserialize)]
+pub struct FieldConfig {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+// Тип Borsh
+#[derive(Debug, Clone)]
+enum Type {
+    Bool,
+    Unsigned(usize),
+    Signed(usize),
+    Pubkey,
+    String,
+    Array(Box<Type>, usize),
+    Vec(Box<Type>),
+    Option(Box<Type>),
+    Struct(Vec<(String, Type)>),
+}
+
+// Готовая к разбору раскладка аккаунта
+#[derive(Debug, Clone)]
+pub struct Layout {
+    skip: usize,
+    fields: Vec<(String, Type)>,
+}
+
+impl Layout {
+    // Разбор данных аккаунта в JSON объект с полями раскладки
+    pub fn decode(&self, data: &[u8]) -> Result<Value> {
+        let mut reader = Reader { data, offset: self.skip };
+        if self.skip > data.len() {
+            anyhow::bail!("Account data is {} bytes, layout skips {}", data.len(), self.skip);
+        }
+        let mut object = Map::new();
+        for (name, kind) in &self.fields {
+            let value = reader.read(kind).with_context(|| format!("Failed to decode field {}", name))?;
+            object.insert(name.clone(), value);
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+// Загрузка всех раскладок; IDL файлы читаются с диска
+pub fn load_all(configs: &HashMap<String, LayoutConfig>) -> Result<HashMap<String, Layout>> {
+    configs
+        .iter()
+        .map(|(name, config)| Ok((name.clone(), load(config).with_context(|| format!("Account layout {}", name))?)))
+        .collect()
+}
+
+fn load(config: &LayoutConfig) -> Result<Layout> {
+    match config {
+        LayoutConfig::Fields { skip, fields } => Ok(Layout {
+            skip: *skip,
+            fields: fields
+                .iter()
+                .map(|field| Ok((field.name.clone(), parse_type(&field.kind)?)))
+                .collect::<Result<_>>()?,
+        }),
+        LayoutConfig::Idl { idl, account } => {
+            let text = fs::read_to_string(idl).with_context(|| format!("Failed to read IDL file: {:?}", idl))?;
+            let idl: Value = serde_json::from_str(&text).with_context(|| format!("Failed to parse IDL file: {:?}", idl))?;
+            from_idl(&idl, account)
+        }
+    }
+}
+
+// Тип из строки конфигурации
+fn parse_type(text: &str) -> Result<Type> {
+    let text = text.trim();
+    let inner = |prefix: &str| text.strip_prefix(prefix).and_then(|rest| rest.strip_suffix('>'));
+
+    if let Some(element) = inner("vec<") {
+        return Ok(Type::Vec(Box::new(parse_type(element)?)));
+    }
+    if let Some(element) = inner("option<") {
+        return Ok(Type::Option(Box::new(parse_type(element)?)));
+    }
+    if let Some(array) = text.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+        let (element, len) = array.rsplit_once(';').with_context(|| format!("Invalid array type: {}", text))?;
+        let len = len.trim().parse().with_context(|| format!("Invalid array length: {}", text))?;
+        return Ok(Type::Array(Box::new(parse_type(element)?), len));
+    }
+    primitive(text).with_context(|| format!("Unknown type: {} (expected e.g. u64, pubkey, string, [u8; 32], vec<u64>, option<pubkey>)", text))
+}
+
+fn primitive(name: &str) -> Option<Type> {
+    Some(match name {
+        "bool" => Type::Bool,
+        "u8" => Type::Unsigned(1),
+        "u16" => Type::Unsigned(2),
+        "u32" => Type::Unsigned(4),
+        "u64" => Type::Unsigned(8),
+        "u128" => Type::Unsigned(16),
+        "i8" => Type::Signed(1),
+        "i16" => Type::Signed(2),
+        "i32" => Type::Signed(4),
+        "i64" => Type::Signed(8),
+        "i128" => Type::Signed(16),
+        "pubkey" | "publicKey" => Type::Pubkey,
+        "string" => Type::String,
+        _ => return None,
+    })
+}
+
+// Раскладка аккаунта из Anchor IDL: поля аккаунта после 8 байт дискриминатора
+fn from_idl(idl: &Value, account: &str) -> Result<Layout> {
+    let type_defs: Vec<&Value> = ["accounts", "types"]
+        .iter()
+        .filter_map(|key| idl.get(key)?.as_array())
+        .flatten()
+        .collect();
+    // IDL Anchor 0.30+ перечисляет в accounts только имена, поля аккаунта описаны в types
+    let find = |name: &str| {
+        type_defs
+            .iter()
+            .copied()
+            .find(|def| def.get("name").and_then(Value::as_str) == Some(name) && def.get("type").is_some())
+    };
+
+    let def = find(account).with_context(|| format!("Account {} not found in IDL", account))?;
+    let Type::Struct(fields) = idl_struct(def, &find, 0)? else {
+        unreachable!("idl_struct returns a struct");
+    };
+
+    Ok(Layout { skip: ANCHOR_DISCRIMINATOR_LEN, fields })
+}
+
+// Структура из определения типа IDL; перечисления не поддерживаются
+fn idl_struct<'a>(def: &Value, find: &impl Fn(&str) -> Option<&'a Value>, depth: usize) -> Result<Type> {
+    let name = def.get("name").and_then(Value::as_str).unwrap_or("?");
+    let body = def.get("type").with_context(|| format!("IDL type {} has no definition", name))?;
+    if body.get("kind").and_then(Value::as_str) != Some("struct") {
+        anyhow::bail!("IDL type {} is not a struct; only struct layouts are supported", name);
+    }
+
+    let fields = body
+        .get("fields")
+        .and_then(Value::as_array)
+        .with_context(|| format!("IDL struct {} has no fields", name))?
+        .iter()
+        .map(|field| {
+            let field_name = field.get("name").and_then(Value::as_str).context("IDL field without name")?;
+            let kind = field.get("type").context("IDL field without type")?;
+            Ok((field_name.to_string(), idl_type(kind, find, depth)?))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Type::Struct(fields))
+}
+
+// Тип поля IDL: строка примитива или объект array / vec / option / defined
+fn idl_type<'a>(kind: &Value, find: &impl Fn(&str) -> Option<&'a Value>, depth: usize) -> Result<Type> {
+    if let Some(name) = kind.as_str() {
+        return primitive(name).with_context(|| format!("Unsupported IDL type: {}", name));
+    }
+    if let Some(array) = kind.get("array").and_then(Value::as_array) {
+        let (Some(element), Some(len)) = (array.first(), array.get(1).and_then(Value::as_u64)) else {
+            anyhow::bail!("Invalid IDL array type: {}", kind);
+        };
+        return Ok(Type::Array(Box::new(idl_type(element, find, depth)?), len as usize));
+    }
+    if let Some(element) = kind.get("vec") {
+        return Ok(Type::Vec(Box::new(idl_type(element, find, depth)?)));
+    }
+    if let Some(element) = kind.get("option") {
+        return Ok(Type::Option(Box::new(idl_type(element, find, depth)?)));
+    }
+    if let Some(defined) = kind.get("defined") {
+        // Старые IDL: "defined": "Name", новые: "defined": { "name": "Name" }
+        let name = defined.as_str().or_else(|| defined.get("name")?.as_str())
+            .with_context(|| format!("Invalid IDL defined type: {}", kind))?;
+        if depth >= MAX_DEFINED_DEPTH {
+            anyhow::bail!("IDL type {} is nested too deeply", name);
+        }
+        let def = find(name).with_context(|| format!("IDL type {} not found", name))?;
+        return idl_struct(def, find, depth + 1);
+    }
+    anyhow::bail!("Unsupported IDL type: {}", kind)
+}
+
+// Последовательное чтение данных аккаунта
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        let bytes = self.offset.checked_add(len)
+            .and_then(|end| self.data.get(self.offset..end))
+            .with_context(|| format!("Account data ends at {} bytes, need {} more at offset {}", self.data.len(), len, self.offset))?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?) as usize)
+    }
+
+    // u64 и меньшие числа - JSON числа; 128-битные - строки, чтобы не терять точность
+    fn read(&mut self, kind: &Type) -> Result<Value> {
+        Ok(match kind {
+            Type::Bool => Value::Bool(self.take(1)?[0] != 0),
+            Type::Unsigned(len) => {
+                let mut bytes = [0u8; 16];
+                bytes[..*len].copy_from_slice(self.take(*len)?);
+                let value = u128::from_le_bytes(bytes);
+                if *len <= 8 {
+                    json!(value as u64)
+                } else {
+                    json!(value.to_string())
+                }
+            }
+            Type::Signed(len) => {
+                // Расширение знака до 128 бит
+                let bytes = self.take(*len)?;
+                let fill = if bytes[len - 1] & 0x80 != 0 { 0xff } else { 0 };
+                let mut wide = [fill; 16];
+                wide[..*len].copy_from_slice(bytes);
+                let value = i128::from_le_bytes(wide);
+                if *len <= 8 {
+                    json!(value as i64)
+                } else {
+                    json!(value.to_string())
+                }
+            }
+            Type::Pubkey => json!(transaction::encode_pubkey(self.take(32)?.try_into()?)),
+            Type::String => {
+                let len = self.read_len()?;
+                json!(String::from_utf8_lossy(self.take(len)?))
+            }
+            Type::Array(element, len) => self.read_many(element, *len)?,
+            Type::Vec(element) => {
+                let len = self.read_len()?;
+                self.read_many(element, len)?
+            }
+            Type::Option(element) => match self.take(1)?[0] {
+                0 => Value::Null,
+                _ => self.read(element)?,
+            },
+            Type::Struct(fields) => {
+                let mut object = Map::new();
+                for (name, kind) in fields {
+                    object.insert(name.clone(), self.read(kind)?);
+                }
+                Value::Object(object)
+            }
+        })
+    }
+
+    fn read_many(&mut self, element: &Type, len: usize) -> Result<Value> {
+        // Длина из данных не должна приводить к огромному выделению памяти
+        if len > self.data.len() {
+            anyhow::bail!("Sequence length {} exceeds account data size {}", len, self.data.len());
+        }
+        (0..len).map(|_| self.read(element)).collect::<Result<Vec<_>>>().map(Value::Array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(fields: &[(&str, &str)]) -> Layout {
+        Layout {
+            skip: 0,
+            fields: fields.iter().map(|(name, kind)| (name.to_string(), parse_type(kind).unwrap())).collect(),
+        }
+    }
+
+    #[test]
+    fn decodes_length_prefixed_and_optional_fields() {
+        let layout = layout(&[
+            ("name", "string"),
+            ("items", "vec<u16>"),
+            ("some", "option<u8>"),
+            ("none", "option<u64>"),
+            ("pair", "[u8; 2]"),
+            ("delta", "i16"),
+            ("big", "u128"),
+        ]);
+        let mut data = vec![3, 0, 0, 0];
+        data.extend_from_slice(b"abc");
+        data.extend_from_slice(&[2, 0, 0, 0, 1, 0, 0x34, 0x12]);
+        data.extend_from_slice(&[1, 7, 0]);
+        data.extend_from_slice(&[9, 10]);
+        data.extend_from_slice(&(-2i16).to_le_bytes());
+        data.extend_from_slice(&u128::MAX.to_le_bytes());
+
+        assert_eq!(
+            layout.decode(&data).unwrap(),
+            json!({
+                "name": "abc",
+                "items": [1, 0x1234],
+                "some": 7,
+                "none": null,
+                "pair": [9, 10],
+                "delta": -2,
+                "big": u128::MAX.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn truncated_data_is_an_error() {
+        let account = layout(&[("amount", "u64"), ("owner", "pubkey")]);
+        assert!(account.decode(&[0; 8]).is_err());
+        assert!(account.decode(&[0; 39]).is_err());
+        assert!(account.decode(&[0; 40]).is_ok());
+
+        // Длина строки больше оставшихся данных
+        assert!(layout(&[("name", "string")]).decode(&[10, 0, 0, 0, b'a']).is_err());
+        // Длина вектора не помещается в данные: ошибка без выделения памяти под u32::MAX элементов
+        assert!(layout(&[("items", "vec<u8>")]).decode(&[0xff, 0xff, 0xff, 0xff]).is_err());
+        // Префикс длины обрезан
+        assert!(layout(&[("items", "vec<u8>")]).decode(&[1, 0]).is_err());
+    }
+
+    #[test]
+    fn skip_beyond_data_is_an_error() {
+        let layout = Layout { skip: ANCHOR_DISCRIMINATOR_LEN, fields: vec![] };
+        assert!(layout.decode(&[0; 4]).is_err());
+        assert_eq!(layout.decode(&[0; 8]).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn rejects_invalid_types() {
+        for text in ["float", "vec<u64", "option<>", "[u8; x]", "[u8]", "vec<unknown>"] {
+            assert!(parse_type(text).is_err(), "{} should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn idl_account_with_defined_type() {
+        let idl = json!({
+            "accounts": [{ "name": "Vault" }],
+            "types": [
+                { "name": "Vault", "type": { "kind": "struct", "fields": [
+                    { "name": "bump", "type": "u8" },
+                    { "name": "limits", "type": { "defined": { "name": "Limits" } } }
+                ] } },
+                { "name": "Limits", "type": { "kind": "struct", "fields": [{ "name": "daily", "type": "u32" }] } }
+            ]
+        });
+        let layout = from_idl(&idl, "Vault").unwrap();
+        let mut data = vec![0; ANCHOR_DISCRIMINATOR_LEN];
+        data.extend_from_slice(&[5, 1, 0, 0, 0]);
+
+        assert_eq!(layout.decode(&data).unwrap(), json!({ "bump": 5, "limits": { "daily": 1 } }));
+        assert!(from_idl(&idl, "Missing").is_err());
+    }
+}
@@ -0,0 +1,86 @@
+// src/lifecycle.rs
+// Состояния кошельков: приостановленные не запрашиваются, архивные исключены отовсюду, но остаются в конфигурации
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::groups::GroupConfig;
+use crate::Config;
+
+// Имена встроенных групп с кошельками в этих состояниях
+pub const PAUSED_GROUP: &str = "paused";
+pub const ARCHIVED_GROUP: &str = "archived";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WalletState {
+    Active,
+    Paused,
+    Archived,
+}
+
+// Кошельки из wallets, выведенные из работы
+#[derive(Debug, Default)]
+pub struct Lifecycle {
+    pub paused: Vec<String>,
+    pub archived: Vec<String>,
+}
+
+impl Lifecycle {
+    // Активные и приостановленные кошельки: по ним продолжают строиться отчеты по истории транзакций
+    pub fn history_wallets(&self, active: &[String]) -> Vec<String> {
+        active.iter().chain(&self.paused).cloned().collect()
+    }
+}
+
+// В wallets и списках адресов групп остаются только активные кошельки;
+// остальные доступны через встроенные группы paused и archived
+pub fn apply(
+    wallets: &mut Vec<String>,
+    groups: &mut HashMap<String, GroupConfig>,
+    states: &HashMap<String, WalletState>,
+) -> Lifecycle {
+    if states.is_empty() {
+        return Lifecycle::default();
+    }
+    let state = |address: &String| states.get(address).copied().unwrap_or(WalletState::Active);
+    let lifecycle = Lifecycle {
+        paused: wallets.iter().filter(|wallet| state(wallet) == WalletState::Paused).cloned().collect(),
+        archived: wallets.iter().filter(|wallet| state(wallet) == WalletState::Archived).cloned().collect(),
+    };
+
+    wallets.retain(|wallet| state(wallet) == WalletState::Active);
+    for group in groups.values_mut() {
+        if let GroupConfig::Addresses(addresses) = group {
+            addresses.retain(|address| state(address) == WalletState::Active);
+        }
+    }
+    for (name, members) in [(PAUSED_GROUP, &lifecycle.paused), (ARCHIVED_GROUP, &lifecycle.archived)] {
+        groups.entry(name.to_string()).or_insert_with(|| GroupConfig::Addresses(members.clone()));
+    }
+
+    lifecycle
+}
+
+// Остальные секции с адресами кошельков: приостановленные не публикуются и не проверяются
+// на баланс и активность, архивные исключены и из отчетов по истории (fee_payers, notarization)
+pub fn apply_sections(config: &mut Config) {
+    let states = &config.wallet_states;
+    if states.is_empty() {
+        return;
+    }
+    let state = |address: &String| states.get(address).copied().unwrap_or(WalletState::Active);
+
+    if let Some(page) = &mut config.status_page {
+        page.wallets.retain(|wallet| state(&wallet.address) == WalletState::Active);
+    }
+    config.heartbeats.retain(|heartbeat| state(&heartbeat.wallet) == WalletState::Active);
+    if config.canary.as_ref().is_some_and(|canary| state(&canary.address) != WalletState::Active) {
+        config.canary = None;
+    }
+    if let Some(fee_payers) = &mut config.fee_payers {
+        fee_payers.wallets.retain(|wallet| state(wallet) != WalletState::Archived);
+    }
+    if config.notarization.as_ref().is_some_and(|notary| state(&notary.wallet) == WalletState::Archived) {
+        config.notarization = None;
+    }
+}
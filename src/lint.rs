@@ -0,0 +1,262 @@
+// src/lint.rs
+// Проверка конфигурации на рискованные настройки без обращения к сети
+use anyhow::Result;
+use std::collections::HashSet;
+
+use crate::debug_bundle;
+use crate::format;
+use crate::groups::{self, GroupConfig};
+use crate::lifecycle;
+use crate::probes;
+use crate::programs;
+use crate::time_zone;
+use crate::transaction;
+use crate::Config;
+
+// Публичные эндпоинты Solana Labs с жесткими лимитами запросов
+const PUBLIC_ENDPOINTS: &[&str] = &["api.mainnet-beta.solana.com", "api.devnet.solana.com", "api.testnet.solana.com"];
+
+// Сколько кошельков публичный эндпоинт выдерживает без ошибок 429
+const PUBLIC_ENDPOINT_WALLETS: usize = 100;
+
+// Минимальный баланс rent-exempt для аккаунта без данных
+const RENT_EXEMPT_MINIMUM: u64 = 890_880;
+
+// Проверка конфигурации; commands - имена команд для проверки секции display
+pub fn run(config: &Config, commands: &[String]) -> Result<()> {
+    let mut warnings = Vec::new();
+
+    if config.rpc_url.is_empty() && config.endpoint_registry.is_none() {
+        warnings.push("neither rpc_url nor endpoint_registry is set; RPC commands will fail".to_string());
+    }
+    if config.rpc_url.is_empty() && config.endpoint_registry.is_some() {
+        warnings.push("only public endpoints from endpoint_registry will be used; set rpc_url to a private endpoint".to_string());
+    }
+
+    let host = url_host(&config.rpc_url);
+    if PUBLIC_ENDPOINTS.contains(&host) && config.wallets.len() > PUBLIC_ENDPOINT_WALLETS {
+        warnings.push(format!(
+            "rpc_url is the public endpoint {} with {} wallets; expect rate limiting above {}, use a dedicated provider",
+            host, config.wallets.len(), PUBLIC_ENDPOINT_WALLETS
+        ));
+    }
+    if config.rpc_url.starts_with("http://") && !matches!(host, "localhost" | "127.0.0.1") {
+        warnings.push("rpc_url uses plain http; responses can be tampered with in transit".to_string());
+    }
+    if let Some(secret) = plaintext_secret(&config.rpc_url) {
+        warnings.push(format!("rpc_url contains {} in plaintext; keep config.yaml out of version control", secret));
+    }
+    if let Some(secret) = config.archive_rpc_url.as_deref().and_then(plaintext_secret) {
+        warnings.push(format!("archive_rpc_url contains {} in plaintext; keep config.yaml out of version control", secret));
+    }
+    for (i, endpoint) in config.endpoints.iter().enumerate() {
+        if let Some(secret) = plaintext_secret(endpoint) {
+            warnings.push(format!("endpoints[{}] contains {} in plaintext; keep config.yaml out of version control", i, secret));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for wallet in &config.wallets {
+        if !seen.insert(wallet) {
+            warnings.push(format!("wallet {} is listed more than once", wallet));
+        } else if let Err(e) = transaction::decode_pubkey(wallet) {
+            warnings.push(format!("wallet {} is not a valid address: {:#}", wallet, e));
+        }
+    }
+    for address in config.wallet_states.keys() {
+        if !config.wallets.contains(address) {
+            warnings.push(format!("wallet_states: {} is not in wallets; keep archived wallets listed instead of deleting them", address));
+        }
+    }
+    if config.wallets.is_empty() {
+        warnings.push("no wallets configured".to_string());
+    }
+
+    if config.max_slot_lag == 0 {
+        warnings.push("max_slot_lag is 0; almost every balance will be reported as STALE".to_string());
+    }
+    if let Some(canary) = &config.canary {
+        if canary.expected_lamports > 0 && canary.expected_lamports < RENT_EXEMPT_MINIMUM {
+            warnings.push(format!(
+                "canary expected_lamports {} is below the rent-exempt minimum {}; such a balance cannot persist",
+                canary.expected_lamports, RENT_EXEMPT_MINIMUM
+            ));
+        }
+    }
+
+    // Группы разрешаются так же, как при запуске команд: без приостановленных и архивных кошельков
+    let mut active = config.wallets.clone();
+    let mut resolved_groups = config.groups.clone();
+    lifecycle::apply(&mut active, &mut resolved_groups, &config.wallet_states);
+    let resolver = groups::Groups::new(&active, &resolved_groups);
+    let mut group_names: Vec<&String> = config.groups.keys().collect();
+    group_names.sort();
+    for name in group_names {
+        if let Err(e) = resolver.resolve(name) {
+            warnings.push(format!("group {}: {:#}", name, e));
+        }
+        if let GroupConfig::Addresses(addresses) = &config.groups[name] {
+            for address in addresses {
+                if let Err(e) = transaction::decode_pubkey(address) {
+                    warnings.push(format!("group {}: {} is not a valid address: {:#}", name, address, e));
+                }
+            }
+        }
+    }
+
+    if let Some(page) = &config.status_page {
+        for wallet in &page.wallets {
+            if let Err(e) = transaction::decode_pubkey(&wallet.address) {
+                warnings.push(format!("status_page: {} is not a valid address: {:#}", wallet.address, e));
+            }
+        }
+        if let Some(group) = &page.group {
+            if let Err(e) = resolver.resolve(group) {
+                warnings.push(format!("status_page group {}: {:#}", group, e));
+            }
+        }
+        for aggregate in &page.aggregates {
+            match resolver.resolve(&aggregate.group) {
+                Err(e) => warnings.push(format!("status_page aggregate {}: {:#}", aggregate.group, e)),
+                Ok(members) if members.len() < aggregate.min_wallets => warnings.push(format!(
+                    "status_page aggregate {} has {} wallets, fewer than min_wallets {}; it will not be published",
+                    aggregate.group, members.len(), aggregate.min_wallets
+                )),
+                Ok(_) => {}
+            }
+        }
+    }
+
+    if let Some(monitor) = &config.program_monitor {
+        for program in &monitor.programs {
+            if let Err(e) = transaction::decode_pubkey(&program.id) {
+                warnings.push(format!("program_monitor: {} is not a valid program id: {:#}", program.id, e));
+            }
+            if let Some(authority) = program.upgrade_authority.as_deref().filter(|authority| *authority != programs::IMMUTABLE) {
+                if let Err(e) = transaction::decode_pubkey(authority) {
+                    warnings.push(format!("program_monitor: upgrade_authority {} is not a valid address: {:#}", authority, e));
+                }
+            }
+        }
+    }
+
+    if let Some(notary) = &config.notarization {
+        if let Err(e) = transaction::decode_pubkey(&notary.wallet) {
+            warnings.push(format!("notarization: wallet {} is not a valid address: {:#}", notary.wallet, e));
+        }
+    }
+
+    for heartbeat in &config.heartbeats {
+        if let Err(e) = transaction::decode_pubkey(&heartbeat.wallet) {
+            warnings.push(format!("heartbeats: {} is not a valid address: {:#}", heartbeat.wallet, e));
+        }
+        if heartbeat.max_silence_minutes <= 0 {
+            warnings.push(format!("heartbeats: {} has max_silence_minutes {}; it will always alert", heartbeat.wallet, heartbeat.max_silence_minutes));
+        }
+    }
+
+    for token in &config.bridged_tokens {
+        if let Err(e) = transaction::decode_pubkey(&token.mint) {
+            warnings.push(format!("bridged_tokens: {} is not a valid mint: {:#}", token.mint, e));
+        }
+    }
+
+    for fetch in &config.fetch_groups {
+        if let Err(e) = resolver.resolve(&fetch.group) {
+            warnings.push(format!("fetch_groups {}: {:#}", fetch.group, e));
+        }
+        if fetch.concurrency == 0 {
+            warnings.push(format!("fetch_groups {} has concurrency 0; 1 is used instead", fetch.group));
+        }
+    }
+
+    let limits = &config.fetch_strategy;
+    if limits.max_accounts_per_request == 0 || limits.max_accounts_per_request > 100 {
+        warnings.push(format!(
+            "fetch_strategy.max_accounts_per_request is {}; RPC nodes accept 1 to 100, the nearest is used instead",
+            limits.max_accounts_per_request
+        ));
+    }
+
+    let mut probe_names = HashSet::new();
+    for probe in &config.probes {
+        if !probe_names.insert(&probe.name) {
+            warnings.push(format!("probe {} is defined more than once", probe.name));
+        }
+        if let Some(alert) = &probe.alert {
+            match (alert.above, alert.below) {
+                (None, None) => warnings.push(format!("probe {} alert has neither above nor below", probe.name)),
+                (Some(above), Some(below)) if above < below => warnings.push(format!(
+                    "probe {} alert fires on every value: above {} is less than below {}",
+                    probe.name, above, below
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    warnings.extend(probes::validate(&config.probes, &config.account_layouts));
+
+    if let Some(name) = &config.timezone {
+        if let Err(e) = time_zone::TimeZone::load(name) {
+            warnings.push(format!("timezone: {:#}; times will be shown in UTC", e));
+        }
+    }
+
+    let display_rules = std::iter::once(("display".to_string(), &config.display.default))
+        .chain(config.display.reports.iter().map(|(report, rules)| (format!("display.reports.{}", report), rules)));
+    for (section, rules) in display_rules {
+        for (field, shown) in [("sol_decimals", rules.sol_decimals), ("usd_decimals", rules.usd_decimals)] {
+            if shown.is_some_and(|shown| shown > format::MAX_DECIMALS) {
+                warnings.push(format!("{}.{} is above {}; {} is used instead", section, field, format::MAX_DECIMALS, format::MAX_DECIMALS));
+            }
+        }
+    }
+    for (report, rules) in &config.display.reports {
+        if report != "balances" && !commands.contains(report) {
+            warnings.push(format!("display.reports.{} does not match any command", report));
+        } else if rules.redact.as_ref().is_some_and(|redact| !redact.is_empty()) && !format::REDACTING_REPORTS.contains(&report.as_str()) {
+            warnings.push(format!("display.reports.{}.redact is set, but {} cannot redact; the command will refuse to run", report, report));
+        }
+    }
+    if config.display.default.redact.as_ref().is_some_and(|redact| !redact.is_empty()) {
+        warnings.push(format!(
+            "display.redact applies to every command, but only {} can redact; other commands will refuse to run",
+            format::REDACTING_REPORTS.join(", ")
+        ));
+    }
+
+    for warning in &warnings {
+        println!("[WARN] {}", warning);
+    }
+    if !warnings.is_empty() {
+        anyhow::bail!("Config lint found {} warnings", warnings.len());
+    }
+    println!("Config lint: no warnings");
+
+    Ok(())
+}
+
+// Хост из URL без схемы, учетных данных и порта
+fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    host.split(':').next().unwrap_or_default()
+}
+
+// Описание секрета в URL: учетные данные или ключ в параметрах запроса
+fn plaintext_secret(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if authority.contains('@') {
+        return Some("credentials".to_string());
+    }
+
+    let query = url.split_once('?')?.1;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('=').map(|(name, _)| name.to_lowercase()))
+        .find(|name| debug_bundle::is_secret_name(name))
+        .map(|name| format!("the {} parameter", name))
+}
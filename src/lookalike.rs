@@ -0,0 +1,78 @@
+// src/lookalike.rs
+// Похожие адреса: опечатки при добавлении кошельков и адреса-двойники для отравления истории
+use anyhow::Result;
+
+// Сколько символов в начале и в конце адреса сравнивается при поиске двойников
+const LOOKALIKE_CHARS: usize = 4;
+
+// Адрес, отличающийся от известного не более чем на столько символов, считается опечаткой
+const TYPO_DISTANCE: usize = 2;
+
+// Разные адреса с одинаковыми первыми и последними символами
+pub fn looks_alike(a: &str, b: &str) -> bool {
+    a != b
+        && a.is_ascii()
+        && b.is_ascii()
+        && a.len() > LOOKALIKE_CHARS * 2
+        && b.len() > LOOKALIKE_CHARS * 2
+        && a[..LOOKALIKE_CHARS] == b[..LOOKALIKE_CHARS]
+        && a[a.len() - LOOKALIKE_CHARS..] == b[b.len() - LOOKALIKE_CHARS..]
+}
+
+// Число вставок, удалений и замен символов между строками (расстояние Левенштейна)
+fn distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// Чем адрес похож на известный; None - не похож или совпадает
+fn similarity(address: &str, known: &str) -> Option<String> {
+    if address == known {
+        return None;
+    }
+    match distance(address, known) {
+        1 => Some("is 1 character away from".to_string()),
+        d if d <= TYPO_DISTANCE => Some(format!("is {} characters away from", d)),
+        _ if looks_alike(address, known) => {
+            Some(format!("has the same first and last {} characters as", LOOKALIKE_CHARS))
+        }
+        _ => None,
+    }
+}
+
+// Предупреждения о новых адресах, похожих на известные или друг на друга; возвращает их число
+pub fn warn_similar(added: &[&str], known: &[String]) -> usize {
+    let mut warnings = 0;
+    for (i, address) in added.iter().enumerate() {
+        let known = known.iter().map(|other| ("known", other.as_str()));
+        let earlier = added[..i].iter().map(|other| ("new", *other));
+        for (kind, other) in known.chain(earlier) {
+            if let Some(how) = similarity(address, other) {
+                warnings += 1;
+                println!("[WARN] {} {} {} address {}", address, how, kind, other);
+            }
+        }
+    }
+    warnings
+}
+
+// Проверка перед добавлением адресов; похожий адрес останавливает добавление без force
+pub fn check_new(added: &[&str], known: &[String], force: bool) -> Result<()> {
+    let warnings = warn_similar(added, known);
+    if warnings > 0 && !force {
+        anyhow::bail!(
+            "{} lookalike addresses found (typo or address poisoning); check them and use --force to add anyway",
+            warnings
+        );
+    }
+    Ok(())
+}
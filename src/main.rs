@@ -1,54 +1,166 @@
 // src/main.rs
+mod account;
+mod address_audit;
+mod backfill;
+mod bench;
+mod bridges;
+mod chaos;
+mod debug_bundle;
+mod check;
+mod cleanup;
+mod components;
+mod deposits;
+mod dex;
+mod discovery;
+mod failed_tx;
+mod fees;
+mod fetch_groups;
+mod fetch_strategy;
+mod flows;
+mod format;
+mod graph;
+mod groups;
+mod heartbeat;
+mod history;
+mod import;
+mod init;
+mod layout;
+mod lifecycle;
+mod lint;
+mod lookalike;
+mod lst;
+mod notarization;
+mod plan;
+mod positions;
+mod preflight;
+mod priority_fees;
+mod probes;
+mod progress;
+mod programs;
+mod registry;
+mod revoke;
+mod router;
+mod run_stats;
+mod rpc;
+mod scenario;
+mod security;
+mod signing;
+mod solana_pay;
+mod stake;
+mod stake_audit;
+mod staking;
+mod status_page;
+mod suggest;
+mod time_zone;
+mod token;
+mod token_anomalies;
+mod transaction;
+mod trend;
+mod triage;
+mod tx;
+mod usage;
+mod vesting;
+
 use anyhow::{Context as AnyhowContext, Result};
 use futures::future::join_all;
 use serde::Deserialize;
 use serde_json::json;
+use std::collections::HashMap;
 use std::fs::File;
 use std::path::PathBuf;
-use clap::Parser;
+use std::time::Duration;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use rand::seq::SliceRandom;
 
 // Структура для хранения конфигурации из YAML
 #[derive(Debug, Deserialize)]
 struct Config {
+    // Пустой, если эндпоинт выбирается из реестра
+    #[serde(default)]
     rpc_url: String,
+    // Реестр публичных эндпоинтов на случай, когда свой эндпоинт не задан
+    endpoint_registry: Option<registry::RegistryConfig>,
+    // Другие эндпоинты того же кластера; запросы идут на самый быстрый из них и rpc_url
+    #[serde(default)]
+    endpoints: Vec<String>,
+    // Архивный эндпоинт провайдера с полной историей транзакций (для --archive)
+    archive_rpc_url: Option<String>,
+    // Учет запросов и кредитов провайдера с дневным лимитом
+    usage: Option<usage::UsageConfig>,
+    // Дополнительные RPC узлы кластера для распределения запросов балансов
+    node_discovery: Option<discovery::DiscoveryConfig>,
     wallets: Vec<String>,
+    // Состояния кошельков по адресу (active, paused, archived); не указанные - active
+    #[serde(default)]
+    wallet_states: HashMap<String, lifecycle::WalletState>,
+    // Именованные группы кошельков: списки адресов или union/intersect/except других групп
+    #[serde(default)]
+    groups: HashMap<String, groups::GroupConfig>,
+    // Параллелизм и бюджет повторов запросов балансов по группам
+    #[serde(default)]
+    fetch_groups: Vec<fetch_groups::FetchGroupConfig>,
+    // Ограничения автоматического выбора способа запроса балансов (--strategy)
+    #[serde(default)]
+    fetch_strategy: fetch_strategy::FetchStrategyConfig,
+    // Публичная страница с балансами выбранных кошельков
+    status_page: Option<status_page::StatusPageConfig>,
+    // Свои токены, часть предложения которых заблокирована в мостах (команда bridges)
+    #[serde(default)]
+    bridged_tokens: Vec<bridges::BridgedToken>,
+    // Программы, полномочие обновления и байткод которых отслеживаются (команда programs)
+    program_monitor: Option<programs::ProgramMonitorConfig>,
+    // Кошелек, публикующий хэши отчетов в memo (команды notarize и verify-notarization)
+    notarization: Option<notarization::NotarizationConfig>,
+    // Кошельки, которые должны совершать транзакции не реже заданного (команда heartbeat)
+    #[serde(default)]
+    heartbeats: Vec<heartbeat::Heartbeat>,
+    // Кошельки-плательщики комиссий с порогами всплеска и дневным бюджетом
+    fee_payers: Option<fees::FeePayersConfig>,
+    // Ряд замеров цены compute unit (команда priority-fees)
+    #[serde(default)]
+    priority_fees: priority_fees::PriorityFeesConfig,
+    // Максимально допустимое отставание слота ответа от последнего слота кластера
+    #[serde(default = "default_max_slot_lag")]
+    max_slot_lag: u64,
+    // Контрольный кошелек с заранее известным балансом
+    canary: Option<Canary>,
+    // Разрешенные полномочия стейк-аккаунтов
+    #[serde(default)]
+    stake_audit: stake_audit::StakeAuditConfig,
+    // Пользовательские RPC методы, значения которых выводит команда probes
+    #[serde(default)]
+    probes: Vec<probes::ProbeConfig>,
+    // Borsh раскладки данных аккаунтов для probes
+    #[serde(default)]
+    account_layouts: HashMap<String, layout::LayoutConfig>,
+    // Локаль для вывода чисел человеку
+    locale: Option<String>,
+    // Часовой пояс для вывода времени и разбивки по дням (IANA, например Europe/Berlin, или строка POSIX TZ)
+    timezone: Option<String>,
+    // Округление и сокращения сумм, в том числе по отдельным отчетам
+    #[serde(default)]
+    display: format::DisplayConfig,
 }
 
-// Структура для парсинга ответа Solana JSON RPC API
-#[derive(Debug, Deserialize)]
-struct RpcResponse {
-    #[allow(dead_code)]
-    jsonrpc: String,
-    result: Option<Balance>,
-    error: Option<RpcError>,
-    #[allow(dead_code)]
-    id: u64,
-}
-
+// Контрольный кошелек для сквозной проверки работоспособности
 #[derive(Debug, Deserialize)]
-struct Balance {
-    value: u64,
-    #[allow(dead_code)]
-    context: BalanceContext,
-}
-
-#[derive(Debug, Deserialize)]
-struct BalanceContext {
-    #[allow(dead_code)]
-    slot: u64,
+struct Canary {
+    address: String,
+    expected_lamports: u64,
 }
 
-#[derive(Debug, Deserialize)]
-struct RpcError {
-    code: i64,
-    message: String,
+// ~1 минута при среднем времени слота 400 мс
+fn default_max_slot_lag() -> u64 {
+    150
 }
 
 // Структура для хранения результатов балансов
 #[derive(Debug)]
 struct WalletBalance {
     address: String,
-    balance: f64,
+    lamports: u64,
+    // Слот, на котором RPC узел вернул баланс (если провайдер его сообщил)
+    slot: Option<u64>,
 }
 
 // Структура для параметров командной строки
@@ -56,26 +168,943 @@ struct WalletBalance {
 #[clap(author, version, about)]
 struct Args {
     /// Путь к файлу конфигурации
-    #[clap(short, long, default_value = "config.yaml")]
+    #[clap(short, long, default_value = "config.yaml", global = true)]
     config: PathBuf,
+
+    /// Независимый RPC эндпоинт для перепроверки балансов
+    #[clap(long, value_name = "ENDPOINT")]
+    verify_with: Option<String>,
+
+    /// Количество случайных кошельков для перепроверки (по умолчанию все)
+    #[clap(long, value_name = "N", requires = "verify_with")]
+    verify_sample: Option<usize>,
+
+    /// Составляющие баланса каждого кошелька: свободные, застейканные и заблокированные SOL
+    #[clap(long)]
+    components: bool,
+
+    /// Способ запроса балансов: per-wallet, multiple-accounts, batch; по умолчанию выбирается автоматически
+    #[clap(long, value_name = "STRATEGY")]
+    strategy: Option<String>,
+
+    /// Локаль для вывода чисел (en, de, fr, ...); по умолчанию без разделителей
+    #[clap(long, global = true)]
+    locale: Option<String>,
+
+    /// Часовой пояс вывода времени (Europe/Berlin или строка POSIX TZ); по умолчанию UTC
+    #[clap(long, global = true)]
+    timezone: Option<String>,
+
+    /// Запросы истории (history, backfill, failed-tx, token-anomalies, fees, graph, suggest, verify-notarization) к archive_rpc_url
+    #[clap(long, global = true)]
+    archive: bool,
+
+    /// Скрыть в выводе для передачи наружу: addresses, balances, labels (через запятую)
+    #[clap(long, global = true, value_name = "WHAT", value_delimiter = ',')]
+    redact: Vec<String>,
+
+    /// В конце запуска вывести время, число RPC запросов, трафик, повторы и пиковую память
+    #[clap(long, global = true)]
+    stats: bool,
+
+    /// Записать те же показатели в файл в формате Prometheus (textfile collector)
+    #[clap(long, global = true, value_name = "FILE")]
+    stats_file: Option<PathBuf>,
+
+    /// Собрать каталог для отчета об ошибке: конфигурация без секретов, итог запуска, время и ошибки RPC запросов
+    #[clap(long, global = true, value_name = "DIR")]
+    debug_bundle: Option<PathBuf>,
+
+    /// Подробный вывод: в том числе почему выбран способ запроса балансов
+    #[clap(short, long, global = true)]
+    verbose: bool,
+
+    /// Перед командой проверить эндпоинты, доступ, пробный баланс и каталоги вывода; прервать запуск при ошибке
+    #[clap(long, global = true)]
+    preflight: bool,
+
+    /// Писать события хода запуска (JSON по строке) в открытый файловый дескриптор, например 3
+    #[clap(long, global = true, value_name = "FD")]
+    progress_fd: Option<u32>,
+
+    /// Писать те же события в unix socket
+    #[clap(long, global = true, value_name = "PATH", conflicts_with = "progress_fd")]
+    progress_socket: Option<PathBuf>,
+
+    /// Случайные ошибки и задержки RPC вызовов для проверки в staging: rate=0.1,latency=500ms
+    #[clap(long, global = true, hide = true, value_name = "SPEC")]
+    inject_failures: Option<String>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+// Дополнительные команды; без команды выводятся балансы кошельков
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Разбор ссылки Solana Pay и ожидание оплаты по reference
+    Pay {
+        /// Ссылка вида solana:<recipient>?amount=..&reference=..
+        url: String,
+
+        /// Только разобрать ссылку, не ожидая оплаты
+        #[clap(long)]
+        parse_only: bool,
+
+        /// Интервал опроса в секундах
+        #[clap(long, default_value_t = 5)]
+        interval: u64,
+
+        /// Максимальное время ожидания в секундах
+        #[clap(long, default_value_t = 300)]
+        timeout: u64,
+    },
+
+    /// Ожидание зарегистрированных входящих платежей на отслеживаемые кошельки
+    WatchDeposits {
+        /// YAML файл с ожидаемыми платежами
+        file: PathBuf,
+
+        /// Интервал опроса в секундах
+        #[clap(long, default_value_t = 10)]
+        interval: u64,
+
+        /// Сколько последних транзакций просматривать при поиске платежа
+        #[clap(long, default_value_t = 100)]
+        scan_limit: usize,
+    },
+
+    /// Последние транзакции адреса с текстом memo
+    History {
+        /// Адрес кошелька
+        address: String,
+
+        /// Количество последних транзакций (не более 1000)
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Показывать только транзакции, memo которых содержит этот текст
+        #[clap(long)]
+        memo: Option<String>,
+
+        /// Искать переводы-пыль с адресов, похожих на известных контрагентов
+        #[clap(long)]
+        check_poisoning: bool,
+
+        /// Скрывать подозрительные переводы из списка (выводятся только в разделе Security)
+        #[clap(long)]
+        hide_poisoned: bool,
+    },
+
+    /// Проверка безопасности кошельков: владелец аккаунта, делегаты токенов, полномочия стейка
+    Security,
+
+    /// Сверка staker/withdrawer стейк-аккаунтов со списком разрешенных ключей
+    StakeAudit,
+
+    /// Снятие активных делегатов с токен-аккаунтов кошелька
+    Revoke {
+        /// Кошелек-владелец токен-аккаунтов
+        wallet: String,
+
+        /// Только указанные токен-аккаунты (по умолчанию все с делегатами)
+        #[clap(long = "account", value_name = "ADDRESS")]
+        accounts: Vec<String>,
+
+        /// JSON файл ключа владельца; без него выводятся неподписанные транзакции
+        #[clap(long)]
+        keypair: Option<PathBuf>,
+
+        /// Отправить подписанные транзакции в сеть
+        #[clap(long, requires = "keypair")]
+        submit: bool,
+    },
+
+    /// Закрытие пустых токен-аккаунтов кошелька с возвратом ренты
+    Cleanup {
+        /// Кошелек-владелец токен-аккаунтов
+        wallet: String,
+
+        /// Только показать пустые аккаунты и сумму ренты
+        #[clap(long, conflicts_with_all = ["keypair", "submit"])]
+        report_only: bool,
+
+        /// JSON файл ключа владельца; без него выводятся неподписанные транзакции
+        #[clap(long)]
+        keypair: Option<PathBuf>,
+
+        /// Отправить подписанные транзакции в сеть
+        #[clap(long, requires = "keypair")]
+        submit: bool,
+    },
+
+    /// Сводка по ренте в пустых токен-аккаунтах всех отслеживаемых кошельков
+    RentReport,
+
+    /// Проверка адресов из конфигурации: формат, принадлежность кривой, владелец аккаунта
+    AuditAddresses,
+
+    /// Сравнение фактического расхода кошельков за период с планом
+    Variance {
+        /// YAML файл плана: период и плановый расход по кошелькам
+        plan: PathBuf,
+    },
+
+    /// Изменения балансов кошельков и их суммы за последние дни: min/max/средний баланс
+    Trend {
+        /// Окна в днях
+        #[clap(long = "window", value_name = "DAYS", default_values_t = [7, 30, 90])]
+        windows: Vec<u32>,
+    },
+
+    /// Стоимость текущих балансов по гипотетическим ценам из файла сценариев
+    Scenario {
+        /// YAML файл: базовые цены в USD и сценарии их изменения
+        file: PathBuf,
+    },
+
+    /// Заблокированные и доступные суммы в контрактах вестинга (Streamflow, Bonfida)
+    Vesting,
+
+    /// Свободные, застейканные и заблокированные на DEX SOL кошельков
+    Staking,
+
+    /// Позиции кошельков в DeFi протоколах (marginfi, Kamino) и их стоимость по данным протокола
+    Positions {
+        /// Только указанные протоколы (по умолчанию все)
+        #[clap(long = "protocol", value_name = "NAME")]
+        protocols: Vec<String>,
+    },
+
+    /// Средства в открытых ордерах OpenBook, заблокированные на DEX
+    OpenOrders,
+
+    /// Предложение токенов из bridged_tokens, заблокированное в мостах (Wormhole), и остаток в обращении на Solana
+    Bridges,
+
+    /// Значения пользовательских RPC методов из секции probes
+    Probes {
+        /// Только указанные методы по имени (по умолчанию все)
+        #[clap(long = "probe", value_name = "NAME")]
+        names: Vec<String>,
+    },
+
+    /// Проверка конфигурации на рискованные настройки
+    Lint,
+
+    /// Быстрая проверка перед долгим запуском: эндпоинты и ключи API, пробный баланс, каталоги вывода
+    Preflight,
+
+    /// Интерактивное создание стартовой конфигурации по пути --config
+    Init {
+        /// Перезаписать существующий файл
+        #[clap(long)]
+        force: bool,
+    },
+
+    /// Быстрая проверка одного адреса; работает и без файла конфигурации
+    Check {
+        /// Адрес для проверки
+        #[clap(required_unless_present = "clipboard")]
+        address: Option<String>,
+
+        /// Взять адрес из буфера обмена
+        #[clap(long, conflicts_with = "address")]
+        clipboard: bool,
+
+        /// RPC эндпоинт; по умолчанию из конфигурации, а без нее публичный mainnet
+        #[clap(long)]
+        rpc_url: Option<String>,
+    },
+
+    /// Накопленное потребление RPC по эндпоинтам и методам (секция usage)
+    Usage {
+        /// Только за последние N дней, включая сегодня
+        #[clap(long)]
+        days: Option<u32>,
+    },
+
+    /// Замеры производительности
+    Bench {
+        #[clap(subcommand)]
+        target: BenchTarget,
+    },
+
+    /// Граф переводов SOL между кошельками и контрагентами по последним транзакциям
+    Graph {
+        #[clap(subcommand)]
+        action: GraphAction,
+    },
+
+    /// Неуспешные транзакции, оплаченные отслеживаемыми кошельками, с ошибкой и логами
+    FailedTx {
+        /// Сколько последних транзакций каждого кошелька проверять (не более 1000)
+        #[clap(long, default_value_t = 100)]
+        limit: usize,
+
+        /// Только транзакции за последние N минут (удобно при запуске по расписанию)
+        #[clap(long, value_name = "MINUTES")]
+        since_minutes: Option<i64>,
+    },
+
+    /// Сводка по списку адресов из файла: баланс, последняя активность, владелец, токены, связи с кошельками
+    Triage {
+        /// Файл с адресами, по одному в строке
+        file: PathBuf,
+
+        /// Сколько последних транзакций каждого адреса просматривать в поиске связей (не более 1000)
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Выгрузка всей истории подписей адреса в JSONL файл; повторный запуск продолжает с контрольной точки
+    Backfill {
+        /// Адрес кошелька
+        address: String,
+
+        /// Каталог для <address>.jsonl и контрольной точки
+        #[clap(long, default_value = "backfill")]
+        dir: PathBuf,
+
+        /// Пауза между страницами по 1000 подписей, мс (чтобы не упираться в лимиты провайдера)
+        #[clap(long, default_value_t = 200)]
+        delay_ms: u64,
+    },
+
+    /// Смена владельца или полномочия закрытия и закрытие токен-аккаунтов кошельков по последним транзакциям
+    TokenAnomalies {
+        /// Сколько последних транзакций кошелька и каждого его токен-аккаунта проверять (не более 1000)
+        #[clap(long, default_value_t = 50)]
+        limit: usize,
+
+        /// Только транзакции за последние N минут (удобно при запуске по расписанию)
+        #[clap(long, value_name = "MINUTES")]
+        since_minutes: Option<i64>,
+    },
+
+    /// Полномочие обновления и хэш байткода программ из program_monitor; [ALERT] при смене или обновлении
+    Programs,
+
+    /// Кошельки из heartbeats, которые дольше ожидаемого не совершали транзакций
+    Heartbeat,
+
+    /// Комиссии кошельков-плательщиков (секция fee_payers) по дням и неделям
+    Fees {
+        /// Период в днях
+        #[clap(long, default_value_t = 30)]
+        days: u32,
+    },
+
+    /// Замер цены compute unit по последним слотам с сохранением в ряд (для запуска по расписанию)
+    PriorityFees {
+        /// Сколько последних замеров ряда вывести
+        #[clap(long, default_value_t = 20)]
+        history: usize,
+    },
+
+    /// Адреса, часто переводящие SOL отслеживаемым кошелькам или получающие от них, как кандидаты в wallets
+    Suggest {
+        /// Сколько последних транзакций каждого кошелька анализировать (не более 1000)
+        #[clap(long, default_value_t = 100)]
+        limit: usize,
+
+        /// Минимальное число переводов, чтобы считать адрес частым контрагентом
+        #[clap(long, default_value_t = 3)]
+        min_transfers: usize,
+
+        /// Спросить по каждому адресу и добавить подтвержденные в wallets
+        #[clap(long)]
+        add: bool,
+
+        /// Добавлять и адреса, похожие на уже известные (опечатка или двойник)
+        #[clap(long, requires = "add")]
+        force: bool,
+    },
+
+    /// Статическая страница (index.html и status.json) с балансами кошельков из секции status_page
+    StatusPage,
+
+    /// Публикация SHA-256 файлов отчетов в memo транзакции от кошелька notarization.wallet
+    Notarize {
+        /// Файлы отчетов (например status/status.json)
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+
+        /// JSON файл ключа нотариуса; без него выводятся неподписанные транзакции
+        #[clap(long)]
+        keypair: Option<PathBuf>,
+
+        /// Отправить подписанные транзакции в сеть
+        #[clap(long, requires = "keypair")]
+        submit: bool,
+    },
+
+    /// Проверка, что файл отчета совпадает с хэшем, опубликованным кошельком notarization.wallet
+    VerifyNotarization {
+        /// Файл отчета
+        file: PathBuf,
+
+        /// Сколько последних транзакций нотариуса просматривать (не более 1000)
+        #[clap(long, default_value_t = 1000)]
+        limit: usize,
+    },
+
+    /// Группы кошельков и операции над ними (wallets - встроенная группа всех кошельков)
+    Wallets {
+        #[clap(subcommand)]
+        op: WalletsOp,
+    },
+
+    /// Импорт адресов кошельков в конфигурацию
+    Import {
+        #[clap(subcommand)]
+        source: ImportSource,
+    },
+}
+
+// Наборы замеров
+#[derive(Subcommand, Debug)]
+enum BenchTarget {
+    /// Разбор ответов, агрегация и вывод на синтетических данных, без сети и конфигурации
+    Internal {
+        /// Размеры наборов в кошельках
+        #[clap(long = "wallets", value_name = "N", default_values_t = [10_000, 100_000, 1_000_000])]
+        sizes: Vec<usize>,
+    },
+}
+
+// Действия с графом переводов
+#[derive(Subcommand, Debug)]
+enum GraphAction {
+    /// Вывод графа в stdout для Graphviz (dot) или Mermaid
+    Export {
+        /// dot или mermaid
+        #[clap(long, default_value = "dot")]
+        format: String,
+
+        /// Сколько последних транзакций каждого кошелька учитывать (не более 1000)
+        #[clap(long, default_value_t = 100)]
+        limit: usize,
+    },
+}
+
+// Операции над группами кошельков
+#[derive(Subcommand, Debug)]
+enum WalletsOp {
+    /// Адреса группы; без имени - список групп с числом адресов
+    List {
+        group: Option<String>,
+    },
+    /// Адреса, входящие хотя бы в одну из групп
+    Union {
+        a: String,
+        b: String,
+    },
+    /// Адреса, входящие в обе группы
+    Intersect {
+        a: String,
+        b: String,
+    },
+    /// Адреса группы a, не входящие в группу b
+    Except {
+        a: String,
+        b: String,
+    },
+}
+
+// Источники импорта адресов
+#[derive(Subcommand, Debug)]
+enum ImportSource {
+    /// CSV выгрузка биржи или таблицы; новые адреса добавляются в wallets
+    Csv {
+        /// CSV файл
+        file: PathBuf,
+
+        /// Колонка с адресом: имя из заголовка или номер начиная с 1
+        #[clap(long, default_value = "address")]
+        address_column: String,
+
+        /// Колонка с меткой, записывается комментарием рядом с адресом
+        #[clap(long)]
+        label_column: Option<String>,
+
+        /// Разделитель колонок
+        #[clap(long, default_value_t = ',')]
+        delimiter: char,
+
+        /// В файле нет строки заголовка
+        #[clap(long)]
+        no_header: bool,
+
+        /// Только показать новые адреса, не меняя конфигурацию
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Добавлять и адреса, похожие на уже известные (опечатка или двойник)
+        #[clap(long)]
+        force: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Парсинг аргументов командной строки
-    let args = Args::parse();
+    // Парсинг аргументов командной строки; имя команды нужно для правил отображения
+    let matches = Args::command().get_matches();
+    let args = Args::from_arg_matches(&matches)?;
+    let report = matches.subcommand_name().unwrap_or("balances");
+    run_stats::start();
+    if args.debug_bundle.is_some() {
+        debug_bundle::enable();
+    }
+    if let Some(fd) = args.progress_fd {
+        progress::open_fd(fd)?;
+    }
+    if let Some(path) = &args.progress_socket {
+        progress::connect(path)?;
+    }
+
+    let result = run(&args, report).await;
+
+    if let Err(e) = &result {
+        progress::error(None, e);
+    }
+    progress::finished(result.is_ok());
+
+    // Итоги и каталог для отчета об ошибке собираются и после неудачного запуска
+    if args.stats {
+        run_stats::print();
+    }
+    if let Some(path) = &args.stats_file {
+        if let Err(e) = run_stats::write_metrics(path, report) {
+            println!("Failed to write run stats: {:#}", e);
+        }
+    }
+    if let Some(bundle) = &args.debug_bundle {
+        if let Err(e) = debug_bundle::write(bundle, &args.config, &result) {
+            println!("Failed to write debug bundle: {:#}", e);
+        }
+    }
+    result
+}
+
+// Выполнение команды
+async fn run(args: &Args, report: &str) -> Result<()> {
+    if let Some(spec) = &args.inject_failures {
+        chaos::set(chaos::FailureInjection::parse(spec)?);
+    }
+
+    // Команда, которая не умеет скрывать данные, не запускается: иначе вывод ушел бы наружу нескрытым
+    if !args.redact.is_empty() && !format::REDACTING_REPORTS.contains(&report) {
+        anyhow::bail!("--redact is not supported by {} (supported: {})", report, format::REDACTING_REPORTS.join(", "));
+    }
+    
+    // Создание конфигурации не требует существующего файла
+    if let Some(Command::Init { force }) = &args.command {
+        return init::run(&args.config, *force);
+    }
+    
+    if let Some(Command::Bench { target: BenchTarget::Internal { sizes } }) = &args.command {
+        return bench::run(sizes);
+    }
+    
+    // Быстрая проверка адреса не требует конфигурации
+    if let Some(Command::Check { address, clipboard, rpc_url }) = &args.command {
+        if let Some(tag) = &args.locale {
+            format::set_locale(format::NumberLocale::parse(tag)?);
+        }
+        let address = match address {
+            Some(address) if !*clipboard => address.clone(),
+            _ => check::read_clipboard()?,
+        };
+        let rpc_url = match rpc_url {
+            Some(url) => url.clone(),
+            None => load_config(&args.config)
+                .ok()
+                .map(|config| config.rpc_url)
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| check::DEFAULT_RPC_URL.to_string()),
+        };
+        return check::run(&rpc_url, &address).await;
+    }
     
     // Загрузка конфигурации
-    let config = load_config(&args.config)?;
+    let mut config = load_config(&args.config)?;
+    // Отчет об использовании читает журнал, не изменяя его
+    if let Some(Command::Usage { days }) = &args.command {
+        return usage::report(config.usage.as_ref(), *days);
+    }
+    if let Some(usage) = config.usage.take() {
+        usage::enable(usage)?;
+    }
+    
+    // Без rpc_url эндпоинт выбирается из реестра; lint, import и wallets к сети не обращаются
+    let needs_rpc = !matches!(args.command, Some(Command::Lint | Command::Import { .. } | Command::Wallets { .. }));
+    if config.rpc_url.is_empty() && needs_rpc {
+        let registry = config.endpoint_registry.as_ref()
+            .context("Config has neither rpc_url nor endpoint_registry")?;
+        config.rpc_url = registry::select_endpoint(registry).await?;
+    }
+    router::set_endpoints(&config.rpc_url, &config.endpoints);
+    
+    // Локаль вывода: аргумент командной строки важнее конфигурации
+    if let Some(tag) = args.locale.as_ref().or(config.locale.as_ref()) {
+        format::set_locale(format::NumberLocale::parse(tag)?);
+    }
+    // Скрытие из командной строки добавляется к настроенному для отчета
+    let mut rules = config.display.rules_for(report);
+    for name in &args.redact {
+        let redact = format::Redact::parse(name)?;
+        let list = rules.redact.get_or_insert_with(Vec::new);
+        if !list.contains(&redact) {
+            list.push(redact);
+        }
+    }
+    let configured = rules.redact.as_ref().is_some_and(|redact| !redact.is_empty());
+    if configured && !format::REDACTING_REPORTS.contains(&report) && !matches!(args.command, Some(Command::Lint)) {
+        anyhow::bail!(
+            "display.redact is set for {}, but it cannot redact its output (supported: {})",
+            report, format::REDACTING_REPORTS.join(", ")
+        );
+    }
+    format::set_rules(rules);
+    // Неизвестный пояс не мешает работе: время выводится в UTC, lint сообщает об ошибке в конфигурации
+    if let Some(name) = args.timezone.as_ref().or(config.timezone.as_ref()).filter(|_| !matches!(args.command, Some(Command::Lint))) {
+        match time_zone::TimeZone::load(name) {
+            Ok(zone) => format::set_time_zone(zone),
+            Err(e) => println!("[WARN] {:#}; times are shown in UTC", e),
+        }
+    }
+    
+    // Приостановленные и архивные кошельки убираются из wallets, групп и секций с адресами; lint и import проверяют
+    // и дополняют конфигурацию как она записана в файле
+    let lifecycle = if matches!(args.command, Some(Command::Lint | Command::Import { .. })) {
+        lifecycle::Lifecycle::default()
+    } else {
+        lifecycle::apply_sections(&mut config);
+        lifecycle::apply(&mut config.wallets, &mut config.groups, &config.wallet_states)
+    };
+    let history_wallets = lifecycle.history_wallets(&config.wallets);
+    progress::started(report, config.wallets.len());
+
+    // Транзакции старше срока хранения обычного узла есть только на архивном эндпоинте
+    let history_url = if args.archive {
+        config.archive_rpc_url.as_deref().context("--archive requires archive_rpc_url in config")?
+    } else {
+        config.rpc_url.as_str()
+    };
+
+    // Ошибка в проверке перед запуском прерывает команду до начала работы
+    if args.preflight && !matches!(args.command, Some(Command::Preflight)) {
+        if let Err(e) = preflight::run(&preflight_targets(args, &config)).await {
+            usage::finish()?;
+            return Err(e);
+        }
+    }
+
+    let result = match &args.command {
+        None => run_balances(args, &config, &lifecycle).await,
+        Some(Command::Pay { url, parse_only, interval, timeout }) => {
+            let request = solana_pay::parse_url(url)?;
+            solana_pay::print_request(&request);
+            if *parse_only {
+                return Ok(());
+            }
+            solana_pay::watch_payment(
+                &config.rpc_url,
+                &request,
+                Duration::from_secs(*interval),
+                Duration::from_secs(*timeout),
+            )
+            .await
+        }
+        Some(Command::WatchDeposits { file, interval, scan_limit }) => {
+            let expected = deposits::load(file, &config.wallets)?;
+            deposits::watch(&config.rpc_url, &expected, Duration::from_secs(*interval), *scan_limit).await
+        }
+        Some(Command::History { address, limit, memo, check_poisoning, hide_poisoned }) => {
+            let options = history::HistoryOptions {
+                limit: *limit,
+                memo_filter: memo.as_deref(),
+                check_poisoning: *check_poisoning,
+                hide_poisoned: *hide_poisoned,
+                known: &history_wallets,
+            };
+            history::print_history(history_url, address, &options).await
+        }
+        Some(Command::Security) => security::run_report(&config.rpc_url, &config.wallets).await,
+        Some(Command::StakeAudit) => stake_audit::run(&config.rpc_url, &config.wallets, &config.stake_audit).await,
+        Some(Command::Revoke { wallet, accounts, keypair, submit }) => {
+            let options = signing::SigningOptions { keypair: keypair.as_deref(), submit: *submit };
+            revoke::run(&config.rpc_url, wallet, accounts, &options).await
+        }
+        Some(Command::Cleanup { wallet, report_only, keypair, submit }) => {
+            let options = signing::SigningOptions { keypair: keypair.as_deref(), submit: *submit };
+            cleanup::run(&config.rpc_url, wallet, *report_only, &options).await
+        }
+        Some(Command::RentReport) => cleanup::rent_report(&config.rpc_url, &config.wallets).await,
+        Some(Command::AuditAddresses) => address_audit::run(&config.rpc_url, &config.wallets).await,
+        Some(Command::Variance { plan }) => {
+            let plan = plan::load(plan)?;
+            plan::variance_report(&config.rpc_url, &plan).await
+        }
+        Some(Command::Trend { windows }) => trend::trend_report(&config.rpc_url, &config.wallets, windows).await,
+        Some(Command::Scenario { file }) => {
+            let scenarios = scenario::load(file)?;
+            scenario::stress_report(&config.rpc_url, &config.wallets, &scenarios).await
+        }
+        Some(Command::Vesting) => vesting::run_report(&config.rpc_url, &config.wallets).await,
+        Some(Command::Staking) => staking::run_report(&config.rpc_url, &config.wallets).await,
+        Some(Command::Positions { protocols }) => {
+            let adapters = positions::select(protocols)?;
+            positions::run_report(&config.rpc_url, &config.wallets, &adapters).await
+        }
+        Some(Command::OpenOrders) => dex::run_report(&config.rpc_url, &config.wallets).await,
+        Some(Command::Bridges) => bridges::run_report(&config.rpc_url, &config.bridged_tokens).await,
+        Some(Command::Probes { names }) => {
+            probes::run_report(&config.rpc_url, &config.wallets, &config.probes, &config.account_layouts, names).await
+        }
+        Some(Command::Lint) => {
+            let commands: Vec<String> = Args::command().get_subcommands().map(|c| c.get_name().to_string()).collect();
+            lint::run(&config, &commands)
+        }
+        Some(Command::Preflight) => preflight::run(&preflight_targets(args, &config)).await,
+        Some(Command::Graph { action: GraphAction::Export { format, limit } }) => {
+            let format = graph::Format::parse(format)?;
+            graph::export(history_url, &history_wallets, *limit, format).await
+        }
+        Some(Command::FailedTx { limit, since_minutes }) => {
+            let since = since_minutes.map(|minutes| chrono::Utc::now().timestamp() - minutes * 60);
+            failed_tx::run_report(history_url, &history_wallets, *limit, since).await
+        }
+        Some(Command::TokenAnomalies { limit, since_minutes }) => {
+            let since = since_minutes.map(|minutes| chrono::Utc::now().timestamp() - minutes * 60);
+            token_anomalies::run_report(history_url, &history_wallets, *limit, since).await
+        }
+        Some(Command::Triage { file, limit }) => triage::run(&config.rpc_url, file, &history_wallets, *limit).await,
+        Some(Command::Backfill { address, dir, delay_ms }) => {
+            backfill::run(history_url, address, dir, Duration::from_millis(*delay_ms)).await
+        }
+        Some(Command::Programs) => {
+            let monitor = config.program_monitor.as_ref().context("Config has no program_monitor section")?;
+            programs::run_report(&config.rpc_url, monitor).await
+        }
+        Some(Command::Heartbeat) => heartbeat::run_report(&config.rpc_url, &config.heartbeats).await,
+        Some(Command::Fees { days }) => {
+            let fee_payers = config.fee_payers.as_ref().context("Config has no fee_payers section")?;
+            fees::run_report(history_url, fee_payers, *days).await
+        }
+        Some(Command::PriorityFees { history }) => {
+            priority_fees::run_report(history_url, &config.priority_fees, *history).await
+        }
+        Some(Command::Suggest { limit, min_transfers, add, force }) => {
+            let known = known_addresses(&config);
+            let options = suggest::SuggestOptions {
+                limit: *limit,
+                min_transfers: *min_transfers,
+                add: *add,
+                force: *force,
+                config_path: &args.config,
+                known: &known,
+            };
+            suggest::run(history_url, &history_wallets, &options).await
+        }
+        Some(Command::StatusPage) => {
+            let page = config.status_page.as_ref().context("Config has no status_page section")?;
+            status_page::generate(&config.rpc_url, page, &config.wallets, &config.groups).await
+        }
+        Some(Command::Notarize { files, keypair, submit }) => {
+            let notary = config.notarization.as_ref().context("Config has no notarization section")?;
+            let options = signing::SigningOptions { keypair: keypair.as_deref(), submit: *submit };
+            notarization::notarize(&config.rpc_url, notary, files, &options).await
+        }
+        Some(Command::VerifyNotarization { file, limit }) => {
+            let notary = config.notarization.as_ref().context("Config has no notarization section")?;
+            notarization::verify(history_url, notary, file, *limit).await
+        }
+        Some(Command::Wallets { op }) => run_wallets(op, &config),
+        Some(Command::Import { source: ImportSource::Csv { file, address_column, label_column, delimiter, no_header, dry_run, force } }) => {
+            let options = import::CsvOptions {
+                address_column,
+                label_column: label_column.as_deref(),
+                delimiter: *delimiter,
+                no_header: *no_header,
+                dry_run: *dry_run,
+                force: *force,
+            };
+            import::import_csv(file, &args.config, &config.wallets, &known_addresses(&config), &options)
+        }
+        Some(Command::Init { .. } | Command::Check { .. } | Command::Bench { .. } | Command::Usage { .. }) => unreachable!("handled before the config is loaded"),
+    };
+    
+    // Итог учета RPC запросов сохраняется и после ошибки команды
+    result.and(usage::finish())
+}
+
+// Что проверять перед запуском: все настроенные эндпоинты и каталоги, куда пишут команды
+fn preflight_targets<'a>(args: &Args, config: &'a Config) -> preflight::Targets<'a> {
+    let parent = |path: &std::path::Path| match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let mut output_dirs = vec![parent(&config.priority_fees.file)];
+    if let Some(page) = &config.status_page {
+        output_dirs.push(page.output.clone());
+    }
+    if let Some(monitor) = &config.program_monitor {
+        output_dirs.push(parent(&monitor.state_file));
+    }
+    if let Some(Command::Backfill { dir, .. }) = &args.command {
+        output_dirs.push(dir.clone());
+    }
+    for file in [&args.stats_file, &args.debug_bundle].into_iter().flatten() {
+        output_dirs.push(parent(file));
+    }
+    output_dirs.sort();
+    output_dirs.dedup();
+
+    preflight::Targets {
+        rpc_url: &config.rpc_url,
+        endpoints: &config.endpoints,
+        archive_rpc_url: config.archive_rpc_url.as_deref(),
+        archive_required: args.archive,
+        sample_wallet: config.wallets.first().map(String::as_str),
+        output_dirs,
+    }
+}
+
+// Все адреса, упомянутые в конфигурации: кошельки, группы и отдельные секции
+fn known_addresses(config: &Config) -> Vec<String> {
+    let mut known = config.wallets.clone();
+    known.extend(config.wallet_states.keys().cloned());
+    for group in config.groups.values() {
+        if let groups::GroupConfig::Addresses(addresses) = group {
+            known.extend(addresses.iter().cloned());
+        }
+    }
+    if let Some(fee_payers) = &config.fee_payers {
+        known.extend(fee_payers.wallets.iter().cloned());
+    }
+    if let Some(page) = &config.status_page {
+        known.extend(page.wallets.iter().map(|wallet| wallet.address.clone()));
+    }
+    known.extend(config.heartbeats.iter().map(|heartbeat| heartbeat.wallet.clone()));
+    if let Some(notary) = &config.notarization {
+        known.push(notary.wallet.clone());
+    }
+    if let Some(canary) = &config.canary {
+        known.push(canary.address.clone());
+    }
+    known.sort();
+    known.dedup();
+    known
+}
+
+// Получение и вывод балансов всех кошельков из конфигурации
+async fn run_balances(args: &Args, config: &Config, lifecycle: &lifecycle::Lifecycle) -> Result<()> {
+    // Основной эндпоинт и найденные узлы кластера, кошельки распределяются между ними
+    let mut endpoints = vec![config.rpc_url.clone()];
+    if let Some(discovery) = &config.node_discovery {
+        match discovery::discover(&config.rpc_url, discovery).await {
+            Ok(nodes) => endpoints.extend(nodes),
+            Err(e) => println!("Node discovery failed, using rpc_url only: {:#}", e),
+        }
+    }
+    
+    // Способ запроса: --strategy или автоматический выбор
+    let forced = args.strategy.as_deref().map(fetch_strategy::Strategy::parse).transpose()?;
+    let grouped = !config.fetch_groups.is_empty();
+    let selection = fetch_strategy::select(forced, &config.fetch_strategy, &config.rpc_url, &config.wallets, grouped).await?;
+    if args.verbose {
+        println!("Fetch strategy: {} ({})", selection.strategy.name(), selection.reason);
+    }
+    
+    // Получение балансов; с fetch_groups - по группам, каждая в своих пределах
+    let balances = match selection.strategy {
+        fetch_strategy::Strategy::PerWallet if grouped => {
+            let groups = groups::Groups::new(&config.wallets, &config.groups);
+            fetch_groups::get_wallet_balances(&endpoints, &config.wallets, &groups, &config.fetch_groups).await?
+        }
+        fetch_strategy::Strategy::PerWallet => get_wallet_balances(&endpoints, &config.wallets).await?,
+        strategy => fetch_strategy::get_wallet_balances(strategy, &config.fetch_strategy, &endpoints, &config.wallets).await,
+    };
+    
+    let components = if args.components {
+        components::get_components(&config.rpc_url, &config.wallets).await
+    } else {
+        HashMap::new()
+    };
     
-    // Получение балансов
-    let balances = get_wallet_balances(&config).await?;
+    // Последний слот кластера для проверки актуальности данных
+    let latest_slot = match get_latest_slot(&config.rpc_url).await {
+        Ok(slot) => Some(slot),
+        Err(e) => {
+            println!("Failed to fetch latest slot, staleness check skipped: {:#}", e);
+            None
+        }
+    };
     
     // Вывод результатов
     println!("Balances for {} wallets:", balances.len());
-    for balance in balances {
-        println!("{}: {} SOL", balance.address, balance.balance);
+    let mut max_lag = 0;
+    for balance in &balances {
+        let lag = match (latest_slot, balance.slot) {
+            (Some(latest), Some(slot)) => latest.saturating_sub(slot),
+            _ => 0,
+        };
+        max_lag = max_lag.max(lag);
+        if lag > config.max_slot_lag {
+            println!("{}: {} SOL (STALE: {} slots behind)", format::address(&balance.address), format::balance(balance.lamports), lag);
+        } else {
+            println!("{}: {} SOL", format::address(&balance.address), format::balance(balance.lamports));
+        }
+        if let Some(components) = components.get(&balance.address) {
+            println!("  {}", components.describe());
+        }
+    }
+    if latest_slot.is_some() {
+        println!("Max slot lag: {} (threshold: {})", max_lag, config.max_slot_lag);
     }
+    if !lifecycle.paused.is_empty() || !lifecycle.archived.is_empty() {
+        println!("Not fetched: {} paused, {} archived wallets", lifecycle.paused.len(), lifecycle.archived.len());
+    }
+    
+    // Перепроверка через независимый эндпоинт
+    if let Some(endpoint) = &args.verify_with {
+        verify_balances(endpoint, &balances, args.verify_sample).await?;
+    }
+    
+    // Сквозная проверка по контрольному кошельку
+    if let Some(canary) = &config.canary {
+        check_canary(&config.rpc_url, canary).await?;
+    }
+    
+    Ok(())
+}
+
+// Операции над группами кошельков из конфигурации
+fn run_wallets(op: &WalletsOp, config: &Config) -> Result<()> {
+    let groups = groups::Groups::new(&config.wallets, &config.groups);
+    let (op, a, b) = match op {
+        WalletsOp::List { group: Some(group) } => {
+            groups::print_addresses(group, &groups.resolve(group)?);
+            return Ok(());
+        }
+        WalletsOp::List { group: None } => {
+            for name in groups.names() {
+                match groups.resolve(name) {
+                    Ok(addresses) => println!("{}: {} addresses", name, addresses.len()),
+                    Err(e) => println!("{}: ERROR {:#}", name, e),
+                }
+            }
+            return Ok(());
+        }
+        WalletsOp::Union { a, b } => (groups::SetOp::Union, a, b),
+        WalletsOp::Intersect { a, b } => (groups::SetOp::Intersect, a, b),
+        WalletsOp::Except { a, b } => (groups::SetOp::Except, a, b),
+    };
+    
+    let addresses = groups::apply(op, &[groups.resolve(a)?, groups.resolve(b)?]);
+    groups::print_addresses(&format!("{} {} {}", a, op.name(), b), &addresses);
     
     Ok(())
 }
@@ -93,51 +1122,38 @@ fn load_config(path: &PathBuf) -> Result<Config> {
 
 // Получение баланса для одного кошелька
 async fn get_single_balance(rpc_url: &str, wallet: &str) -> Result<WalletBalance> {
-    let client = reqwest::Client::new();
-    
-    let request_body = json!({
-        "jsonrpc": "2.0",
-        "id": 1,
-        "method": "getBalance",
-        "params": [wallet]
-    });
-    
-    let response = client.post(rpc_url)
-        .json(&request_body)
-        .send()
+    let balance: rpc::WithContext<u64> = rpc::call_with_context(rpc_url, "getBalance", json!([wallet]))
         .await
-        .with_context(|| format!("Failed to request balance for wallet: {}", wallet))?;
-    
-    let rpc_response: RpcResponse = response.json().await
-        .with_context(|| format!("Failed to parse response for wallet: {}", wallet))?;
-    
-    if let Some(error) = rpc_response.error {
-        anyhow::bail!("RPC error for wallet {}: {} (code: {})", wallet, error.message, error.code);
-    }
-    
-    let balance = rpc_response.result
-        .with_context(|| format!("No balance result for wallet: {}", wallet))?;
-    
-    // Преобразование в SOL (1 SOL = 1_000_000_000 lamports)
-    let sol_balance = balance.value as f64 / 1_000_000_000.0;
+        .with_context(|| format!("Failed to fetch balance for wallet: {}", wallet))?;
     
     Ok(WalletBalance {
         address: wallet.to_string(),
-        balance: sol_balance,
+        lamports: balance.value,
+        slot: balance.slot,
     })
 }
 
-// Получение балансов для всех кошельков параллельно
-async fn get_wallet_balances(config: &Config) -> Result<Vec<WalletBalance>> {
+// Получение последнего слота кластера
+async fn get_latest_slot(rpc_url: &str) -> Result<u64> {
+    rpc::call(rpc_url, "getSlot", json!([])).await
+}
+
+// Получение балансов для всех кошельков параллельно; кошельки по очереди распределяются по эндпоинтам
+async fn get_wallet_balances(endpoints: &[String], wallets: &[String]) -> Result<Vec<WalletBalance>> {
     let mut tasks = Vec::new();
     
-    for wallet in &config.wallets {
-        let rpc_url = config.rpc_url.clone();
+    for (i, wallet) in wallets.iter().enumerate() {
+        let rpc_url = endpoints[i % endpoints.len()].clone();
         let wallet_clone = wallet.clone();
         
         // Создаем задачу для каждого кошелька
         let task = tokio::spawn(async move {
-            get_single_balance(&rpc_url, &wallet_clone).await
+            let result = get_single_balance(&rpc_url, &wallet_clone).await;
+            match &result {
+                Ok(balance) => progress::wallet_done(&balance.address, balance.lamports, balance.slot),
+                Err(e) => progress::error(Some(&wallet_clone), e),
+            }
+            result
         });
         
         tasks.push(task);
@@ -151,10 +1167,67 @@ async fn get_wallet_balances(config: &Config) -> Result<Vec<WalletBalance>> {
     for (i, result) in results.into_iter().enumerate() {
         match result {
             Ok(Ok(balance)) => balances.push(balance),
-            Ok(Err(e)) => println!("Error fetching balance for wallet {}: {}", config.wallets[i], e),
-            Err(e) => println!("Task error for wallet {}: {}", config.wallets[i], e),
+            Ok(Err(e)) => println!("Error fetching balance for wallet {}: {:#}", wallets[i], e),
+            Err(e) => println!("Task error for wallet {}: {}", wallets[i], e),
         }
     }
+    progress::cycle_done(wallets.len(), balances.len());
     
     Ok(balances)
-}
\ No newline at end of file
+}
+
+// Проверка контрольного кошелька: любая ошибка означает, что мониторинг сломан
+async fn check_canary(rpc_url: &str, canary: &Canary) -> Result<()> {
+    let balance = get_single_balance(rpc_url, &canary.address).await
+        .with_context(|| format!("MONITORING BROKEN: canary {} could not be fetched", canary.address))?;
+    
+    if balance.lamports != canary.expected_lamports {
+        anyhow::bail!(
+            "MONITORING BROKEN: canary {} has {} lamports, expected {}",
+            canary.address, balance.lamports, canary.expected_lamports
+        );
+    }
+    println!("Canary check passed: {}", canary.address);
+    
+    Ok(())
+}
+
+// Перепроверка балансов (всех или случайной выборки) через второй эндпоинт
+async fn verify_balances(endpoint: &str, balances: &[WalletBalance], sample: Option<usize>) -> Result<()> {
+    let mut selected: Vec<&WalletBalance> = balances.iter().collect();
+    if let Some(n) = sample {
+        selected.shuffle(&mut rand::thread_rng());
+        selected.truncate(n);
+    }
+    
+    let wallets: Vec<String> = selected.iter().map(|b| b.address.clone()).collect();
+    let verified = get_wallet_balances(&[endpoint.to_string()], &wallets).await?;
+    
+    println!("Verification against {}: {} wallets checked", endpoint, verified.len());
+    let mut discrepancies = 0;
+    for original in &selected {
+        let Some(other) = verified.iter().find(|b| b.address == original.address) else {
+            continue;
+        };
+        if original.lamports != other.lamports {
+            discrepancies += 1;
+            println!(
+                "MISMATCH {}: {} SOL (slot {}) vs {} SOL (slot {})",
+                format::address(&original.address), format::balance(original.lamports), format_slot(original.slot),
+                format::balance(other.lamports), format_slot(other.slot)
+            );
+        }
+    }
+    
+    if discrepancies > 0 {
+        anyhow::bail!("Verification failed: {} discrepancies found", discrepancies);
+    }
+    println!("Verification passed: no discrepancies");
+    
+    Ok(())
+}
+
+// Слот для вывода; провайдер мог его не сообщить
+fn format_slot(slot: Option<u64>) -> String {
+    slot.map_or_else(|| "unknown".to_string(), |slot| slot.to_string())
+}
@@ -0,0 +1,83 @@
+// src/notarization.rs
+// Публикация SHA-256 отчетов в memo транзакциях кошелька-нотариуса и проверка файла по ним
+use anyhow::{Context as AnyhowContext, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::flows;
+use crate::format;
+use crate::signing::{self, SigningOptions};
+use crate::transaction::{self, Instruction};
+use crate::tx;
+
+const MEMO_PROGRAM: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+// Начало текста memo, по которому записи нотариуса отличаются от прочих memo
+const MEMO_PREFIX: &str = "solana_balances sha256:";
+
+// Секция notarization конфигурации
+#[derive(Debug, Deserialize)]
+pub struct NotarizationConfig {
+    // Кошелек, от имени которого публикуются хэши; его адрес сообщается тем, кто проверяет отчеты
+    pub wallet: String,
+}
+
+fn memo_text(file: &Path) -> Result<String> {
+    let content = fs::read(file).with_context(|| format!("Failed to read {:?}", file))?;
+    Ok(format!("{}{:x}", MEMO_PREFIX, Sha256::digest(content)))
+}
+
+// Memo с хэшем каждого файла; транзакции выдаются как в revoke и cleanup
+pub async fn notarize(rpc_url: &str, config: &NotarizationConfig, files: &[PathBuf], options: &SigningOptions<'_>) -> Result<()> {
+    let mut instructions = Vec::new();
+    for file in files {
+        let text = memo_text(file)?;
+        println!("{:?}: {}", file, text);
+        instructions.push(Instruction {
+            program_id: transaction::decode_pubkey(MEMO_PROGRAM)?,
+            accounts: Vec::new(),
+            data: text.into_bytes(),
+        });
+    }
+
+    signing::deliver(rpc_url, &config.wallet, instructions, options).await
+}
+
+// Поиск memo с хэшем файла среди последних limit транзакций нотариуса.
+// Засчитываются только успешные транзакции, подписанные нотариусом: memo может добавить
+// кто угодно, упомянув его адрес в другой инструкции
+pub async fn verify(rpc_url: &str, config: &NotarizationConfig, file: &Path, limit: usize) -> Result<()> {
+    let text = memo_text(file)?;
+    let signatures = tx::get_signatures(rpc_url, &config.wallet, limit).await?;
+    let candidates: Vec<&tx::SignatureInfo> = signatures
+        .iter()
+        .filter(|info| info.err.is_none() && info.memos().contains(&text.as_str()))
+        .collect();
+
+    for (info, transaction) in tx::get_transactions(rpc_url, candidates).await {
+        let payer = match transaction.and_then(|transaction| flows::account_keys(&transaction)) {
+            Ok(keys) => keys.into_iter().next(),
+            Err(e) => {
+                println!("Skipping transaction {}: {:#}", info.signature, e);
+                continue;
+            }
+        };
+        if payer.as_deref() != Some(config.wallet.as_str()) {
+            println!("Ignoring {}: memo matches, but it is not signed by the notary", info.signature);
+            continue;
+        }
+        let time = info.block_time.map_or_else(|| "unknown time".to_string(), format::time);
+        println!(
+            "VERIFIED {:?}: notarized by {} in {} (slot {}, {})",
+            file, config.wallet, info.signature, info.slot, time
+        );
+        return Ok(());
+    }
+
+    anyhow::bail!(
+        "{:?} is not notarized by {} in its last {} transactions ({}); the file may have been altered",
+        file, config.wallet, limit, text
+    )
+}
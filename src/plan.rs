@@ -0,0 +1,98 @@
+// src/plan.rs
+// Сравнение фактических изменений балансов с плановыми расходами
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::fs::File;
+use std::path::Path;
+
+use crate::format;
+use crate::solana_pay;
+use crate::tx;
+
+// Файл плана на период
+#[derive(Debug, Deserialize)]
+pub struct Plan {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub wallets: Vec<PlannedSpend>,
+}
+
+// Плановый расход кошелька за период в SOL (отрицательный - плановый приход)
+#[derive(Debug, Deserialize)]
+pub struct PlannedSpend {
+    pub address: String,
+    pub expected_spend: String,
+}
+
+pub fn load(path: &Path) -> Result<Plan> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open plan file: {:?}", path))?;
+
+    let plan: Plan = serde_yaml::from_reader(file)
+        .with_context(|| "Failed to parse plan file")?;
+    if plan.from >= plan.to {
+        anyhow::bail!("Plan period is empty: {} is not before {}", plan.from, plan.to);
+    }
+
+    Ok(plan)
+}
+
+// Отчет об отклонениях факта от плана по каждому кошельку
+pub async fn variance_report(rpc_url: &str, plan: &Plan) -> Result<()> {
+    println!("Variance report for {} .. {}:", plan.from, plan.to);
+
+    for planned in &plan.wallets {
+        let expected = parse_signed_sol(&planned.expected_spend)
+            .with_context(|| format!("Invalid expected_spend for {}", planned.address))?;
+
+        match actual_spend(rpc_url, &planned.address, plan).await {
+            Ok(actual) => {
+                let variance = actual - expected;
+                let percent = if expected != 0 {
+                    format!(" ({:+.1}%)", variance as f64 * 100.0 / expected.unsigned_abs() as f64)
+                } else {
+                    String::new()
+                };
+                println!(
+                    "{}: planned spend {} SOL, actual {} SOL, variance {} SOL{}",
+                    planned.address,
+                    format::signed_sol(expected),
+                    format::signed_sol(actual),
+                    format::signed_sol(variance),
+                    percent
+                );
+            }
+            Err(e) => println!("Error computing spend for wallet {}: {:#}", planned.address, e),
+        }
+    }
+
+    Ok(())
+}
+
+// Фактический расход за период: сумма изменений баланса во всех транзакциях периода со знаком минус
+async fn actual_spend(rpc_url: &str, address: &str, plan: &Plan) -> Result<i64> {
+    let signatures = tx::get_signatures_since(rpc_url, address, plan.from.timestamp()).await?;
+    let in_period: Vec<&tx::SignatureInfo> = signatures
+        .iter()
+        .filter(|info| info.block_time.is_none_or(|time| time < plan.to.timestamp()))
+        .collect();
+
+    let mut change = 0;
+    for (_, transaction) in tx::get_transactions(rpc_url, in_period).await {
+        change += tx::sol_delta(&transaction?, address)?;
+    }
+
+    Ok(-change)
+}
+
+// Сумма в SOL со знаком в лампортах
+fn parse_signed_sol(amount: &str) -> Result<i64> {
+    let (negative, digits) = match amount.trim().strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, amount.trim()),
+    };
+    let lamports = solana_pay::parse_decimal_amount(digits, solana_pay::SOL_DECIMALS)? as i64;
+
+    Ok(if negative { -lamports } else { lamports })
+}
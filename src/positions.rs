@@ -0,0 +1,188 @@
+// src/positions.rs
+// Позиции кошельков в DeFi протоколах: аккаунты протокола, владельцем которых указан кошелек
+use anyhow::{Context as AnyhowContext, Result};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::account::{self, KeyedAccount};
+use crate::format;
+use crate::rpc;
+
+// Разбор стоимости позиции из данных аккаунта
+type ValueDecoder = fn(&[u8]) -> Option<PositionValue>;
+
+// Адаптер протокола: программа, тип и размер аккаунта позиции, смещение поля владельца
+#[derive(Debug)]
+pub struct Adapter {
+    pub name: &'static str,
+    program: &'static str,
+    // Имя аккаунта Anchor, из которого считается дискриминатор
+    account: &'static str,
+    data_size: u64,
+    owner_offset: usize,
+    // Разбор стоимости позиции; None - протокол только обнаруживается
+    value: Option<ValueDecoder>,
+}
+
+pub const ADAPTERS: &[Adapter] = &[
+    // MarginfiAccount: дискриминатор, group, authority. Стоимость не считается: балансы хранятся в долях
+    // банков, для пересчета нужны аккаунты банков и цены оракулов
+    Adapter {
+        name: "marginfi",
+        program: "MFv2hWf31Z9kbCa1snEPYctwafyhdvnV7FYnmdQ8o5gk",
+        account: "MarginfiAccount",
+        data_size: 2312,
+        owner_offset: 40,
+        value: None,
+    },
+    // Obligation Kamino Lend: дискриминатор, tag, last_update, lending_market, owner
+    Adapter {
+        name: "kamino",
+        program: "KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD",
+        account: "Obligation",
+        data_size: 3344,
+        owner_offset: 64,
+        value: Some(kamino_value),
+    },
+];
+
+// Стоимость позиции в USD по данным самого протокола (на момент последнего обновления)
+#[derive(Debug, Clone, Copy)]
+pub struct PositionValue {
+    pub deposited: f64,
+    pub borrowed: f64,
+}
+
+// Найденная позиция
+#[derive(Debug)]
+pub struct Position {
+    pub protocol: &'static str,
+    pub address: String,
+    pub value: Option<PositionValue>,
+}
+
+// Смещения итоговых сумм в Obligation Kamino; значения хранятся как u128 с дробной частью 2^60
+const KAMINO_DEPOSITED_VALUE: usize = 1192;
+const KAMINO_BORROWED_VALUE: usize = 2224;
+const KAMINO_FRACTION_BITS: i32 = 60;
+
+fn kamino_value(data: &[u8]) -> Option<PositionValue> {
+    let scaled = |offset: usize| {
+        let raw = u128::from_le_bytes(data.get(offset..offset + 16)?.try_into().ok()?);
+        Some(raw as f64 / 2f64.powi(KAMINO_FRACTION_BITS))
+    };
+
+    Some(PositionValue {
+        deposited: scaled(KAMINO_DEPOSITED_VALUE)?,
+        borrowed: scaled(KAMINO_BORROWED_VALUE)?,
+    })
+}
+
+// Первые 8 байт sha256("account:<имя>"), с которых Anchor начинает данные аккаунта
+fn discriminator(account: &str) -> String {
+    let hash = Sha256::digest(format!("account:{}", account));
+    bs58::encode(&hash[..8]).into_string()
+}
+
+// Адаптеры по именам; пустой список - все адаптеры
+pub fn select(names: &[String]) -> Result<Vec<&'static Adapter>> {
+    if names.is_empty() {
+        return Ok(ADAPTERS.iter().collect());
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            ADAPTERS
+                .iter()
+                .find(|adapter| adapter.name.eq_ignore_ascii_case(name))
+                .with_context(|| format!("Unknown protocol: {} (supported: {})", name, supported()))
+        })
+        .collect()
+}
+
+fn supported() -> String {
+    ADAPTERS.iter().map(|adapter| adapter.name).collect::<Vec<_>>().join(", ")
+}
+
+// Позиции кошелька в выбранных протоколах
+pub async fn get_positions(rpc_url: &str, wallet: &str, adapters: &[&'static Adapter]) -> Result<Vec<Position>> {
+    let mut positions = Vec::new();
+
+    for adapter in adapters {
+        let accounts: Vec<KeyedAccount> = rpc::call(
+            rpc_url,
+            "getProgramAccounts",
+            json!([adapter.program, {
+                "encoding": "base64",
+                "filters": [
+                    { "dataSize": adapter.data_size },
+                    { "memcmp": { "offset": 0, "bytes": discriminator(adapter.account) } },
+                    { "memcmp": { "offset": adapter.owner_offset, "bytes": wallet } }
+                ]
+            }]),
+        )
+        .await
+        .with_context(|| format!("Failed to fetch {} positions of {}", adapter.name, wallet))?;
+
+        for keyed in accounts {
+            let value = adapter.value.and_then(|value| value(&account::raw_data(&keyed.account)?));
+            positions.push(Position { protocol: adapter.name, address: keyed.pubkey, value });
+        }
+    }
+
+    Ok(positions)
+}
+
+// Отчет по всем кошелькам
+pub async fn run_report(rpc_url: &str, wallets: &[String], adapters: &[&'static Adapter]) -> Result<()> {
+    let (mut total_deposited, mut total_borrowed) = (0.0, 0.0);
+    let mut unvalued = 0;
+
+    for wallet in wallets {
+        println!("DeFi positions of {}:", wallet);
+        let positions = match get_positions(rpc_url, wallet, adapters).await {
+            Ok(positions) => positions,
+            Err(e) => {
+                println!("  Error: {:#}", e);
+                continue;
+            }
+        };
+        if positions.is_empty() {
+            println!("  no positions");
+        }
+
+        for position in &positions {
+            match position.value {
+                Some(value) => {
+                    total_deposited += value.deposited;
+                    total_borrowed += value.borrowed;
+                    println!(
+                        "  {} {}: deposited {}, borrowed {}, net {}",
+                        position.protocol,
+                        position.address,
+                        format::usd(value.deposited),
+                        format::usd(value.borrowed),
+                        format::usd(value.deposited - value.borrowed)
+                    );
+                }
+                None => {
+                    unvalued += 1;
+                    println!("  {} {}: UNVALUED (not included in totals)", position.protocol, position.address);
+                }
+            }
+        }
+    }
+
+    println!(
+        "Total valued positions: deposited {}, borrowed {}, net {}",
+        format::usd(total_deposited),
+        format::usd(total_borrowed),
+        format::usd(total_deposited - total_borrowed)
+    );
+    if unvalued > 0 {
+        println!("{} positions are unvalued and not included in the totals", unvalued);
+    }
+
+    Ok(())
+}
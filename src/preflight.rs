@@ -0,0 +1,132 @@
+// src/preflight.rs
+// Быстрая проверка перед долгим запуском: эндпоинты и доступ к ним, пробный запрос баланса, каталоги вывода
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::debug_bundle;
+use crate::format;
+use crate::rpc;
+
+// Проверка дольше этого считается неудачной: медленный эндпоинт сорвет и сам запуск
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Что проверяется перед запуском
+pub struct Targets<'a> {
+    pub rpc_url: &'a str,
+    // Дополнительные эндпоинты; без них запуск продолжится на rpc_url
+    pub endpoints: &'a [String],
+    pub archive_rpc_url: Option<&'a str>,
+    // Архивный эндпоинт обязателен, если команда запущена с --archive
+    pub archive_required: bool,
+    pub sample_wallet: Option<&'a str>,
+    // Каталоги, куда команды пишут результаты
+    pub output_dirs: Vec<PathBuf>,
+}
+
+// Итоги проверок: критические ошибки прерывают запуск, предупреждения - нет
+#[derive(Default)]
+struct Outcome {
+    failures: usize,
+    warnings: usize,
+}
+
+impl Outcome {
+    fn report(&mut self, name: &str, critical: bool, result: Result<String>) {
+        match result {
+            Ok(detail) => println!("[OK] {}: {}", name, detail),
+            Err(e) if critical => {
+                self.failures += 1;
+                println!("[FAIL] {}: {:#}", name, e);
+            }
+            Err(e) => {
+                self.warnings += 1;
+                println!("[WARN] {}: {:#}", name, e);
+            }
+        }
+    }
+}
+
+async fn timed<T>(check: impl Future<Output = Result<T>>) -> Result<T> {
+    tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("no response within {:?}", CHECK_TIMEOUT)))
+}
+
+// Состояние узла и версия; отказ в доступе обычно означает неверный или просроченный ключ API
+async fn check_endpoint(endpoint: &str) -> Result<String> {
+    let started = Instant::now();
+    let checked = timed(async {
+        let health: String = rpc::call_direct(endpoint, "getHealth", json!([])).await?;
+        let version: Value = rpc::call_direct(endpoint, "getVersion", json!([])).await?;
+        Ok((health, version))
+    })
+    .await;
+    let (health, version) = checked.map_err(|e| {
+        let text = format!("{:#}", e);
+        if text.contains("HTTP 401") || text.contains("HTTP 403") {
+            e.context("access denied, check the API key in the URL")
+        } else {
+            e
+        }
+    })?;
+    if health != "ok" {
+        anyhow::bail!("getHealth returned {}", health);
+    }
+
+    Ok(format!(
+        "healthy, solana-core {}, {} ms",
+        version.get("solana-core").and_then(Value::as_str).unwrap_or("unknown"),
+        started.elapsed().as_millis()
+    ))
+}
+
+async fn check_sample(rpc_url: &str, wallet: &str) -> Result<String> {
+    let balance: rpc::WithContext<u64> = timed(rpc::call_with_context(rpc_url, "getBalance", json!([wallet]))).await?;
+    Ok(format!("{} SOL", format::balance(balance.value)))
+}
+
+// Каталог создается заранее, как это сделала бы команда, и проверяется пробной записью
+fn check_output_dir(dir: &PathBuf) -> Result<String> {
+    fs::create_dir_all(dir).map_err(|e| anyhow::anyhow!("cannot create: {}", e))?;
+    let probe = dir.join(format!(".preflight-{}", std::process::id()));
+    fs::write(&probe, b"").map_err(|e| anyhow::anyhow!("not writable: {}", e))?;
+    let _ = fs::remove_file(&probe);
+    Ok("writable".to_string())
+}
+
+// Все проверки; ошибка, если не прошла хотя бы одна критическая
+pub async fn run(targets: &Targets<'_>) -> Result<()> {
+    println!("Pre-flight checks:");
+    let mut outcome = Outcome::default();
+
+    let name = format!("rpc_url {}", debug_bundle::scrub(targets.rpc_url));
+    outcome.report(&name, true, check_endpoint(targets.rpc_url).await);
+    for endpoint in targets.endpoints {
+        let name = format!("endpoint {}", debug_bundle::scrub(endpoint));
+        outcome.report(&name, false, check_endpoint(endpoint).await);
+    }
+    if let Some(archive) = targets.archive_rpc_url {
+        let name = format!("archive_rpc_url {}", debug_bundle::scrub(archive));
+        outcome.report(&name, targets.archive_required, check_endpoint(archive).await);
+    }
+
+    match targets.sample_wallet {
+        Some(wallet) => outcome.report(&format!("sample wallet {}", format::address(wallet)), true, check_sample(targets.rpc_url, wallet).await),
+        None => outcome.report("sample wallet", false, Err(anyhow::anyhow!("no wallets configured"))),
+    }
+
+    for dir in &targets.output_dirs {
+        outcome.report(&format!("output {:?}", dir), true, check_output_dir(dir));
+    }
+
+    if outcome.failures > 0 {
+        anyhow::bail!("Pre-flight failed: {} critical checks failed, {} warnings", outcome.failures, outcome.warnings);
+    }
+    println!("Pre-flight passed ({} warnings)", outcome.warnings);
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+// src/priority_fees.rs
+// Цена compute unit по getRecentPrioritizationFees: текущие перцентили и ряд замеров между запусками
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::format;
+use crate::rpc;
+
+// Секция priority_fees конфигурации
+#[derive(Debug, Deserialize)]
+pub struct PriorityFeesConfig {
+    // Файл ряда замеров, по строке JSON на запуск
+    #[serde(default = "default_file")]
+    pub file: PathBuf,
+    // Аккаунты, с которыми пишут свои транзакции; цена считается по блокам с записью в них
+    #[serde(default)]
+    pub accounts: Vec<String>,
+}
+
+impl Default for PriorityFeesConfig {
+    fn default() -> Self {
+        PriorityFeesConfig { file: default_file(), accounts: Vec::new() }
+    }
+}
+
+fn default_file() -> PathBuf {
+    PathBuf::from("priority-fees.jsonl")
+}
+
+// Запись из ответа getRecentPrioritizationFees
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SlotFee {
+    slot: u64,
+    prioritization_fee: u64,
+}
+
+// Один замер: перцентили цены в микролампортах за compute unit по последним слотам
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Sample {
+    pub time: i64,
+    pub first_slot: u64,
+    pub last_slot: u64,
+    pub min: u64,
+    pub median: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub max: u64,
+}
+
+// Замер по последним слотам (RPC узел хранит около 150)
+pub async fn sample(rpc_url: &str, accounts: &[String]) -> Result<Sample> {
+    let params = if accounts.is_empty() { json!([]) } else { json!([accounts]) };
+    let fees: Vec<SlotFee> = rpc::call(rpc_url, "getRecentPrioritizationFees", params).await?;
+    if fees.is_empty() {
+        anyhow::bail!("getRecentPrioritizationFees returned no slots");
+    }
+
+    let mut prices: Vec<u64> = fees.iter().map(|fee| fee.prioritization_fee).collect();
+    prices.sort_unstable();
+    let percentile = |p: usize| prices[(prices.len() - 1) * p / 100];
+
+    Ok(Sample {
+        time: Utc::now().timestamp(),
+        first_slot: fees.iter().map(|fee| fee.slot).min().unwrap_or_default(),
+        last_slot: fees.iter().map(|fee| fee.slot).max().unwrap_or_default(),
+        min: percentile(0),
+        median: percentile(50),
+        p75: percentile(75),
+        p90: percentile(90),
+        max: percentile(100),
+    })
+}
+
+// Добавление замера в конец ряда
+fn append(path: &Path, sample: &Sample) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open priority fee series: {:?}", path))?;
+    writeln!(file, "{}", serde_json::to_string(sample)?)
+        .with_context(|| format!("Failed to write priority fee series: {:?}", path))
+}
+
+// Ряд замеров из файла; отсутствующий файл - пустой ряд
+pub fn load(path: &Path) -> Result<Vec<Sample>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(path).with_context(|| format!("Failed to read priority fee series: {:?}", path))?;
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            serde_json::from_str(line).with_context(|| format!("Invalid sample on line {} of {:?}", i + 1, path))
+        })
+        .collect()
+}
+
+// Новый замер с сохранением в ряд и вывод последних history замеров
+pub async fn run_report(rpc_url: &str, config: &PriorityFeesConfig, history: usize) -> Result<()> {
+    let current = sample(rpc_url, &config.accounts).await?;
+    append(&config.file, &current)?;
+
+    println!(
+        "Priority fees, slots {}..{} (micro-lamports per CU): min {}, median {}, p75 {}, p90 {}, max {}",
+        current.first_slot, current.last_slot, current.min, current.median, current.p75, current.p90, current.max
+    );
+
+    if history == 0 {
+        return Ok(());
+    }
+    let series = load(&config.file)?;
+    println!("Last {} samples from {:?}:", history.min(series.len()), config.file);
+    println!("  {:<40} {:>10} {:>10} {:>10} {:>10}", "time", "median", "p75", "p90", "max");
+    for sample in series.iter().skip(series.len().saturating_sub(history)) {
+        println!("  {:<40} {:>10} {:>10} {:>10} {:>10}", format::time(sample.time), sample.median, sample.p75, sample.p90, sample.max);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,376 @@
+// src/probes.rs
+// Пользовательские RPC методы из конфигурации: шаблон параметров, путь к значению, единица
+use anyhow::{Context as AnyhowContext, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::account;
+use crate::layout::{self, Layout, LayoutConfig};
+use crate::rpc;
+
+// Подстановка адреса кошелька в строках шаблона параметров
+const WALLET_PLACEHOLDER: &str = "{wallet}";
+
+// Элемент секции probes конфигурации
+#[derive(Debug, Deserialize)]
+pub struct ProbeConfig {
+    pub name: String,
+    // RPC метод; не нужен, если задан layout
+    #[serde(default)]
+    pub method: String,
+    // Параметры вызова; строки с {wallet} дают отдельный вызов на каждый кошелек
+    #[serde(default = "default_params")]
+    pub params: Value,
+    // Аккаунт, данные которого разбираются по раскладке layout из account_layouts;
+    // путь тогда указывает на поле разобранного аккаунта, например $.total_deposits
+    pub account: Option<String>,
+    pub layout: Option<String>,
+    // Путь к значению в поле result, например $.value[0].account.lamports
+    pub path: String,
+    // Единица для вывода
+    #[serde(default)]
+    pub unit: String,
+    // Пороги для числового значения
+    pub alert: Option<AlertRule>,
+}
+
+// Правило оповещения: значение выше above или ниже below
+#[derive(Debug, Deserialize)]
+pub struct AlertRule {
+    pub above: Option<f64>,
+    pub below: Option<f64>,
+}
+
+impl AlertRule {
+    // Описание нарушенного порога; None, если значение в допустимых пределах
+    pub fn check(&self, value: f64) -> Option<String> {
+        match (self.above, self.below) {
+            (Some(above), _) if value > above => Some(format!("above {}", above)),
+            (_, Some(below)) if value < below => Some(format!("below {}", below)),
+            _ => None,
+        }
+    }
+}
+
+fn default_params() -> Value {
+    Value::Array(Vec::new())
+}
+
+// Шаг пути: ключ объекта или индекс массива
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+// Подмножество JSONPath: $, .key, ['key'], [n]
+#[derive(Debug, Clone)]
+pub struct JsonPath(Vec<Segment>);
+
+impl JsonPath {
+    pub fn parse(path: &str) -> Result<Self> {
+        let invalid = || format!("Invalid path '{}' (expected e.g. $.value[0].lamports)", path);
+        let mut rest = path.trim().strip_prefix('$').with_context(invalid)?;
+        let mut segments = Vec::new();
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('.') {
+                let end = after.find(['.', '[', ']']).unwrap_or(after.len());
+                if end == 0 {
+                    anyhow::bail!(invalid());
+                }
+                segments.push(Segment::Key(after[..end].to_string()));
+                rest = &after[end..];
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let (inner, after) = after.split_once(']').with_context(invalid)?;
+                let quoted = inner
+                    .strip_prefix('\'').and_then(|key| key.strip_suffix('\''))
+                    .or_else(|| inner.strip_prefix('"').and_then(|key| key.strip_suffix('"')));
+                segments.push(match quoted {
+                    Some(key) => Segment::Key(key.to_string()),
+                    None => Segment::Index(inner.trim().parse().with_context(invalid)?),
+                });
+                rest = after;
+            } else {
+                anyhow::bail!(invalid());
+            }
+        }
+
+        Ok(JsonPath(segments))
+    }
+
+    // Значение по пути; None, если его нет в ответе
+    pub fn extract<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.0.iter().try_fold(value, |value, segment| match segment {
+            Segment::Key(key) => value.get(key),
+            Segment::Index(index) => value.get(index),
+        })
+    }
+}
+
+// Числовое значение; суммы токенов RPC отдает строками
+pub fn numeric(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.parse().ok(),
+        Value::Bool(flag) => Some(f64::from(u8::from(*flag))),
+        _ => None,
+    }
+}
+
+// Результат одного вызова пользовательского метода
+#[derive(Debug)]
+pub struct ProbeReading<'a> {
+    pub probe: &'a ProbeConfig,
+    // Кошелек, подставленный в параметры; None для вызова без {wallet}
+    pub wallet: Option<String>,
+    pub value: Result<Value>,
+}
+
+impl ProbeReading<'_> {
+    // Имя метода и кошелек, если он подставлялся
+    pub fn label(&self) -> String {
+        match &self.wallet {
+            Some(wallet) => format!("{} {}", self.probe.name, wallet),
+            None => self.probe.name.clone(),
+        }
+    }
+}
+
+impl ProbeConfig {
+    fn uses_wallet(&self) -> bool {
+        self.params.to_string().contains(WALLET_PLACEHOLDER)
+            || self.account.as_ref().is_some_and(|account| account.contains(WALLET_PLACEHOLDER))
+    }
+
+    // Вызов метода (или чтение аккаунта по раскладке) с подстановкой кошелька и извлечение значения по пути
+    async fn read(&self, rpc_url: &str, compiled: &CompiledProbe<'_>, wallet: Option<&str>) -> Result<Value> {
+        let template = match &self.account {
+            Some(account) => Value::String(account.clone()),
+            None => self.params.clone(),
+        };
+        let params = match wallet {
+            Some(wallet) => substitute(&template, wallet),
+            None => template,
+        };
+
+        let (result, source) = match compiled.layout {
+            Some(layout) => {
+                let address = params.as_str().unwrap_or_default();
+                let data = account::get_account_data(rpc_url, address).await?;
+                (layout.decode(&data).with_context(|| format!("Failed to decode account {}", address))?, "account")
+            }
+            None => (rpc::call(rpc_url, &self.method, params).await?, self.method.as_str()),
+        };
+        compiled.path.extract(&result)
+            .cloned()
+            .with_context(|| format!("Path {} not found in {} result", self.path, source))
+    }
+}
+
+// Замена {wallet} во всех строках шаблона
+fn substitute(template: &Value, wallet: &str) -> Value {
+    match template {
+        Value::String(text) => Value::String(text.replace(WALLET_PLACEHOLDER, wallet)),
+        Value::Array(items) => Value::Array(items.iter().map(|item| substitute(item, wallet)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields.iter().map(|(key, value)| (key.clone(), substitute(value, wallet))).collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+// Путь к значению и раскладка аккаунта, проверенные до обращения к сети
+struct CompiledProbe<'a> {
+    path: JsonPath,
+    layout: Option<&'a Layout>,
+}
+
+// Проверка одного метода: путь, источник значения, ссылка на раскладку
+fn compile<'a>(probe: &ProbeConfig, layouts: &'a HashMap<String, Layout>) -> Result<CompiledProbe<'a>> {
+    let path = JsonPath::parse(&probe.path)?;
+    let layout = match (&probe.layout, &probe.account) {
+        (Some(name), Some(_)) => Some(layouts.get(name).with_context(|| format!("Unknown account layout: {}", name))?),
+        (Some(_), None) => anyhow::bail!("layout requires account"),
+        (None, Some(_)) => anyhow::bail!("account requires layout"),
+        (None, None) if probe.method.is_empty() => anyhow::bail!("either method or account with layout is required"),
+        (None, None) => None,
+    };
+    Ok(CompiledProbe { path, layout })
+}
+
+// Проверка всех методов без обращения к сети; ошибка - текст предупреждения для lint
+pub fn validate(probes: &[ProbeConfig], layouts: &HashMap<String, LayoutConfig>) -> Vec<String> {
+    let loaded = match layout::load_all(layouts) {
+        Ok(loaded) => loaded,
+        Err(e) => return vec![format!("{:#}", e)],
+    };
+    probes
+        .iter()
+        .filter_map(|probe| compile(probe, &loaded).err().map(|e| format!("probe {}: {:#}", probe.name, e)))
+        .collect()
+}
+
+// Вызов всех методов: по одному на кошелек для шаблонов с {wallet}, иначе один раз
+pub async fn read_all<'a>(
+    rpc_url: &str,
+    wallets: &[String],
+    probes: &[&'a ProbeConfig],
+    layouts: &HashMap<String, Layout>,
+) -> Result<Vec<ProbeReading<'a>>> {
+    let compiled = probes
+        .iter()
+        .map(|probe| compile(probe, layouts).with_context(|| format!("Probe {}", probe.name)))
+        .collect::<Result<Vec<_>>>()?;
+    let mut readings = Vec::new();
+
+    for (&probe, compiled) in probes.iter().zip(&compiled) {
+        let targets: Vec<Option<&str>> = if probe.uses_wallet() {
+            wallets.iter().map(|wallet| Some(wallet.as_str())).collect()
+        } else {
+            vec![None]
+        };
+        let values = futures::future::join_all(targets.iter().map(|wallet| probe.read(rpc_url, compiled, *wallet))).await;
+        for (wallet, value) in targets.into_iter().zip(values) {
+            readings.push(ProbeReading { probe, wallet: wallet.map(str::to_string), value });
+        }
+    }
+
+    Ok(readings)
+}
+
+// Вывод значений пользовательских методов; значения за порогами alert выводятся как [ALERT]
+pub async fn run_report(
+    rpc_url: &str,
+    wallets: &[String],
+    probes: &[ProbeConfig],
+    layouts: &HashMap<String, LayoutConfig>,
+    only: &[String],
+) -> Result<()> {
+    if let Some(unknown) = only.iter().find(|name| !probes.iter().any(|probe| &probe.name == *name)) {
+        anyhow::bail!("Unknown probe: {}", unknown);
+    }
+    let selected: Vec<&ProbeConfig> = probes.iter().filter(|probe| only.is_empty() || only.contains(&probe.name)).collect();
+    if selected.is_empty() {
+        println!("No probes configured");
+        return Ok(());
+    }
+
+    let layouts = layout::load_all(layouts)?;
+
+    let (mut failed, mut alerts) = (0, 0);
+    for reading in read_all(rpc_url, wallets, &selected, &layouts).await? {
+        let label = reading.label();
+        let value = match &reading.value {
+            Ok(value) => value,
+            Err(e) => {
+                failed += 1;
+                println!("{}: ERROR {:#}", label, e);
+                continue;
+            }
+        };
+        println!("{}: {}", label, with_unit(&display(value), &reading.probe.unit));
+
+        let Some(rule) = &reading.probe.alert else {
+            continue;
+        };
+        match numeric(value) {
+            Some(number) => {
+                if let Some(violation) = rule.check(number) {
+                    alerts += 1;
+                    println!("[ALERT] {} is {}, {}", label, with_unit(&number.to_string(), &reading.probe.unit), violation);
+                }
+            }
+            None => {
+                alerts += 1;
+                println!("[ALERT] {} is not numeric: {}", label, display(value));
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} probe calls failed, {} alerts", failed, alerts);
+    }
+    if alerts > 0 {
+        anyhow::bail!("Probes raised {} alerts", alerts);
+    }
+
+    Ok(())
+}
+
+// Строки без кавычек, остальное как JSON
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn with_unit(value: &str, unit: &str) -> String {
+    if unit.is_empty() {
+        value.to_string()
+    } else {
+        format!("{} {}", value, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn segments(path: &str) -> Vec<Segment> {
+        JsonPath::parse(path).unwrap().0
+    }
+
+    fn key(name: &str) -> Segment {
+        Segment::Key(name.to_string())
+    }
+
+    #[test]
+    fn parses_supported_paths() {
+        assert_eq!(segments("$"), []);
+        assert_eq!(segments("$.a.b"), [key("a"), key("b")]);
+        assert_eq!(segments("$.value[0].lamports"), [key("value"), Segment::Index(0), key("lamports")]);
+        assert_eq!(segments("$[2][10]"), [Segment::Index(2), Segment::Index(10)]);
+        assert_eq!(segments("$['a.b'][\"c\"]"), [key("a.b"), key("c")]);
+    }
+
+    #[test]
+    fn extracts_values() {
+        let result = json!({ "value": [{ "account": { "lamports": 5 } }], "a": { "b": "7" } });
+        let extract = |path: &str| JsonPath::parse(path).unwrap().extract(&result).cloned();
+
+        assert_eq!(extract("$.value[0].account.lamports"), Some(json!(5)));
+        assert_eq!(extract("$.a.b").as_ref().and_then(numeric), Some(7.0));
+        assert_eq!(extract("$.value[1]"), None);
+        assert_eq!(extract("$.missing.b"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_paths() {
+        for path in ["", "value", "$a", "$.", "$..a", "$.a.", "$[", "$[0", "$[x]", "$[-1]", "$.a]"] {
+            assert!(JsonPath::parse(path).is_err(), "{:?} should be rejected", path);
+        }
+    }
+
+    #[test]
+    fn validate_reports_invalid_probes() {
+        let probes: Vec<ProbeConfig> = serde_yaml::from_str(
+            r#"
+            - { name: ok, method: getSlot, path: "$" }
+            - { name: bad_path, method: getSlot, path: "$.value[" }
+            - { name: no_source, path: "$.a" }
+            - { name: no_layout, account: "11111111111111111111111111111111", path: "$.a" }
+            "#,
+        )
+        .unwrap();
+        let warnings = validate(&probes, &HashMap::new());
+
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(warnings[0].starts_with("probe bad_path: Invalid path"));
+        assert!(warnings[1].starts_with("probe no_source:"));
+        assert!(warnings[2].starts_with("probe no_layout: account requires layout"));
+    }
+}
@@ -0,0 +1,149 @@
+// src/programs.rs
+// Полномочие обновления и хэш байткода программ: [ALERT] при смене полномочия или обновлении программы
+use anyhow::{Context as AnyhowContext, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::account;
+
+const BPF_UPGRADEABLE_LOADER: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+// Значение upgrade_authority для программ, которые нельзя обновить
+pub const IMMUTABLE: &str = "none";
+
+// Секция program_monitor конфигурации
+#[derive(Debug, Deserialize)]
+pub struct ProgramMonitorConfig {
+    // Последнее увиденное состояние программ; сравнивается при следующем запуске
+    #[serde(default = "default_state_file")]
+    pub state_file: PathBuf,
+    pub programs: Vec<WatchedProgram>,
+}
+
+fn default_state_file() -> PathBuf {
+    PathBuf::from("program-state.json")
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WatchedProgram {
+    pub id: String,
+    pub label: Option<String>,
+    // Ожидаемое полномочие обновления; none - программа должна быть неизменяемой
+    pub upgrade_authority: Option<String>,
+}
+
+// Состояние программы на момент проверки
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ProgramState {
+    authority: Option<String>,
+    // Слот последнего развертывания из ProgramData
+    deployed_slot: u64,
+    // SHA-256 байткода без завершающих нулей
+    hash: String,
+}
+
+// Полномочие, слот и хэш из аккаунта ProgramData программы загрузчика BPF Upgradeable
+async fn fetch_state(rpc_url: &str, id: &str) -> Result<ProgramState> {
+    let program = account::get_account_info(rpc_url, id).await?.with_context(|| format!("Program {} not found", id))?;
+    if program.owner != BPF_UPGRADEABLE_LOADER {
+        anyhow::bail!("{} is owned by {}, not the upgradeable loader", id, program.owner);
+    }
+    let program_data = account::parsed_info(&program)
+        .and_then(|info| info.get("programData"))
+        .and_then(Value::as_str)
+        .with_context(|| format!("Program {} has no programData address", id))?;
+
+    let data = account::get_account_info(rpc_url, program_data)
+        .await?
+        .with_context(|| format!("ProgramData {} not found", program_data))?;
+    let info = account::parsed_info(&data).with_context(|| format!("ProgramData {} has no parsed data", program_data))?;
+    let bytecode = info
+        .pointer("/data/0")
+        .and_then(Value::as_str)
+        .and_then(|encoded| base64::engine::general_purpose::STANDARD.decode(encoded).ok())
+        .with_context(|| format!("ProgramData {} has no bytecode", program_data))?;
+    let length = bytecode.iter().rposition(|&byte| byte != 0).map_or(0, |last| last + 1);
+
+    Ok(ProgramState {
+        authority: info.get("authority").and_then(Value::as_str).map(str::to_string),
+        deployed_slot: info.get("slot").and_then(Value::as_u64).unwrap_or_default(),
+        hash: format!("{:x}", Sha256::digest(&bytecode[..length])),
+    })
+}
+
+fn load(path: &Path) -> Result<BTreeMap<String, ProgramState>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let text = fs::read_to_string(path).with_context(|| format!("Failed to read program state: {:?}", path))?;
+    serde_json::from_str(&text).with_context(|| format!("Invalid program state: {:?}", path))
+}
+
+// Замена целиком, чтобы прерванная запись не стерла известное состояние
+fn save(path: &Path, states: &BTreeMap<String, ProgramState>) -> Result<()> {
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, serde_json::to_string_pretty(states)?).with_context(|| format!("Failed to write {:?}", temp))?;
+    fs::rename(&temp, path).with_context(|| format!("Failed to replace program state {:?}", path))
+}
+
+// Проверка программ с сохранением состояния; каждое изменение выводится как [ALERT] один раз
+pub async fn run_report(rpc_url: &str, config: &ProgramMonitorConfig) -> Result<()> {
+    let mut states = load(&config.state_file)?;
+    let mut alerts = 0;
+
+    for program in &config.programs {
+        let name = program.label.as_deref().unwrap_or(&program.id);
+        let current = match fetch_state(rpc_url, &program.id).await {
+            Ok(current) => current,
+            Err(e) => {
+                alerts += 1;
+                println!("[ALERT] {}: check failed: {:#}", name, e);
+                continue;
+            }
+        };
+        let authority = current.authority.as_deref().unwrap_or(IMMUTABLE);
+        println!(
+            "{}: upgrade authority {}, deployed at slot {}, hash {}",
+            name, authority, current.deployed_slot, current.hash
+        );
+
+        let mut found = Vec::new();
+        if let Some(expected) = &program.upgrade_authority {
+            if expected != authority {
+                found.push(format!("upgrade authority is {}, expected {}", authority, expected));
+            }
+        }
+        if let Some(previous) = states.get(&program.id) {
+            if previous.authority != current.authority {
+                found.push(format!(
+                    "upgrade authority changed from {} to {}",
+                    previous.authority.as_deref().unwrap_or(IMMUTABLE), authority
+                ));
+            }
+            if previous.hash != current.hash {
+                found.push(format!(
+                    "program upgraded at slot {} (hash {} -> {})",
+                    current.deployed_slot, previous.hash, current.hash
+                ));
+            }
+        }
+        for alert in found {
+            alerts += 1;
+            println!("[ALERT] {} ({}): {}", name, program.id, alert);
+        }
+        states.insert(program.id.clone(), current);
+    }
+    save(&config.state_file, &states)?;
+
+    if alerts > 0 {
+        anyhow::bail!("{} program alerts", alerts);
+    }
+    println!("No changes in {} watched programs", config.programs.len());
+
+    Ok(())
+}
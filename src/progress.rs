@@ -0,0 +1,65 @@
+// src/progress.rs
+// События хода запуска для внешних оркестраторов: JSON по строке на событие в файловый дескриптор или unix socket.
+// Набор событий и полей стабилен: started, wallet_done, cycle_done, error, finished
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use crate::debug_bundle;
+
+static SINK: OnceLock<Mutex<Box<dyn Write + Send>>> = OnceLock::new();
+
+// Дескриптор, открытый запускающим процессом (например 3>progress.jsonl)
+pub fn open_fd(fd: u32) -> Result<()> {
+    let path = format!("/dev/fd/{}", fd);
+    let file = OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("Progress descriptor {} is not open for writing", fd))?;
+    let _ = SINK.set(Mutex::new(Box::new(file)));
+    Ok(())
+}
+
+pub fn connect(path: &Path) -> Result<()> {
+    let stream = UnixStream::connect(path).with_context(|| format!("Failed to connect to progress socket {:?}", path))?;
+    let _ = SINK.set(Mutex::new(Box::new(stream)));
+    Ok(())
+}
+
+// Без --progress-fd и --progress-socket ничего не делает; ошибки записи не прерывают запуск
+fn emit(event: &str, mut fields: Value) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    fields["event"] = json!(event);
+    fields["time"] = json!(Utc::now().to_rfc3339());
+    let mut sink = sink.lock().unwrap();
+    let _ = writeln!(sink, "{}", fields).and_then(|_| sink.flush());
+}
+
+pub fn started(command: &str, wallets: usize) {
+    emit("started", json!({ "command": command, "wallets": wallets }));
+}
+
+pub fn wallet_done(wallet: &str, lamports: u64, slot: Option<u64>) {
+    emit("wallet_done", json!({ "wallet": wallet, "lamports": lamports, "slot": slot }));
+}
+
+// Один проход запроса балансов; перепроверка через --verify-with - отдельный проход
+pub fn cycle_done(wallets: usize, fetched: usize) {
+    emit("cycle_done", json!({ "wallets": wallets, "fetched": fetched, "failed": wallets - fetched }));
+}
+
+// Ошибка по кошельку или всего запуска (wallet = null); URL в тексте без ключей
+pub fn error(wallet: Option<&str>, error: &anyhow::Error) {
+    emit("error", json!({ "wallet": wallet, "message": debug_bundle::scrub(&format!("{:#}", error)) }));
+}
+
+pub fn finished(ok: bool) {
+    emit("finished", json!({ "ok": ok }));
+}
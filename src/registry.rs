@@ -0,0 +1,78 @@
+// src/registry.rs
+// Список публичных RPC эндпоинтов из внешнего реестра и выбор рабочего из них
+use anyhow::{Context as AnyhowContext, Result};
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::rpc;
+
+// Секция endpoint_registry конфигурации
+#[derive(Debug, Deserialize)]
+pub struct RegistryConfig {
+    // JSON файл реестра
+    pub url: String,
+    // Кластер, эндпоинты которого подходят
+    #[serde(default = "default_cluster")]
+    pub cluster: String,
+}
+
+fn default_cluster() -> String {
+    "mainnet-beta".to_string()
+}
+
+// Формат реестра: { "endpoints": [ { "url", "cluster", "healthy", ... } ] }
+#[derive(Debug, Deserialize)]
+struct Registry {
+    endpoints: Vec<RegistryEndpoint>,
+}
+
+// Эндпоинт с метаданными о состоянии, которые поддерживает владелец реестра
+#[derive(Debug, Deserialize)]
+struct RegistryEndpoint {
+    url: String,
+    #[serde(default = "default_cluster")]
+    cluster: String,
+    // Эндпоинты, помеченные в реестре как нерабочие, не используются
+    #[serde(default = "default_healthy")]
+    healthy: bool,
+}
+
+fn default_healthy() -> bool {
+    true
+}
+
+// Эндпоинты кластера из реестра в случайном порядке (нагрузка распределяется между запусками),
+// первый ответивший на getHealth используется для всего запуска
+pub async fn select_endpoint(config: &RegistryConfig) -> Result<String> {
+    let registry: Registry = reqwest::get(&config.url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to fetch endpoint registry {}", config.url))?
+        .json()
+        .await
+        .with_context(|| format!("Endpoint registry {} has unexpected format", config.url))?;
+
+    let mut candidates: Vec<String> = registry
+        .endpoints
+        .into_iter()
+        .filter(|endpoint| endpoint.healthy && endpoint.cluster == config.cluster)
+        .map(|endpoint| endpoint.url)
+        .collect();
+    if candidates.is_empty() {
+        anyhow::bail!("Endpoint registry {} has no healthy {} endpoints", config.url, config.cluster);
+    }
+    candidates.shuffle(&mut rand::thread_rng());
+
+    for url in &candidates {
+        match rpc::call::<String>(url, "getHealth", json!([])).await {
+            Ok(_) => {
+                println!("Using RPC endpoint {} from registry", url);
+                return Ok(url.clone());
+            }
+            Err(e) => println!("Skipping registry endpoint {}: {:#}", url, e),
+        }
+    }
+
+    anyhow::bail!("None of {} {} endpoints from the registry passed the health check", candidates.len(), config.cluster)
+}
@@ -0,0 +1,43 @@
+// src/revoke.rs
+// Снятие активных делегатов с токен-аккаунтов кошелька
+use anyhow::Result;
+
+use crate::signing::{self, SigningOptions};
+use crate::token;
+use crate::transaction;
+
+// Поиск аккаунтов с делегатами и выдача транзакций Revoke
+pub async fn run(rpc_url: &str, wallet: &str, only: &[String], options: &SigningOptions<'_>) -> Result<()> {
+    let delegated: Vec<token::TokenAccount> = token::get_token_accounts(rpc_url, wallet)
+        .await?
+        .into_iter()
+        .filter(|account| account.delegate.is_some())
+        .filter(|account| only.is_empty() || only.contains(&account.address))
+        .collect();
+
+    for address in only {
+        if !delegated.iter().any(|account| &account.address == address) {
+            println!("Skipping {}: not a delegated token account of {}", address, wallet);
+        }
+    }
+    if delegated.is_empty() {
+        println!("No token accounts with active delegates to revoke");
+        return Ok(());
+    }
+
+    let owner = transaction::decode_pubkey(wallet)?;
+    let mut instructions = Vec::new();
+    println!("Revoking {} delegates:", delegated.len());
+    for account in &delegated {
+        println!(
+            "  {} (mint {}): delegate {} for {} base units",
+            account.address,
+            account.mint,
+            account.delegate.as_deref().unwrap_or_default(),
+            account.delegated_amount
+        );
+        instructions.push(token::revoke_instruction(account, &owner)?);
+    }
+
+    signing::deliver(rpc_url, wallet, instructions, options).await
+}
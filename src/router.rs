@@ -0,0 +1,144 @@
+// src/router.rs
+// Выбор самого быстрого из нескольких настроенных RPC эндпоинтов с гистерезисом
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::rpc;
+
+// Вес нового замера в скользящем среднем задержки
+const EWMA_WEIGHT: f64 = 0.3;
+
+// Переключение только если другой эндпоинт быстрее текущего хотя бы на эту долю
+const HYSTERESIS: f64 = 0.2;
+
+// Эндпоинт с ошибкой не используется это время
+const FAILURE_COOLDOWN: Duration = Duration::from_secs(30);
+
+// Как часто перемеряются эндпоинты, на которые сейчас не идут запросы
+const PROBE_INTERVAL: Duration = Duration::from_secs(15);
+
+// Состояние одного эндпоинта
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    // Средняя задержка в миллисекундах; None - еще не измерялась
+    latency_ms: Option<f64>,
+    unhealthy_until: Option<Instant>,
+}
+
+impl Endpoint {
+    fn healthy(&self, now: Instant) -> bool {
+        self.unhealthy_until.is_none_or(|until| until <= now)
+    }
+}
+
+#[derive(Debug)]
+struct Router {
+    // Адрес, запросы к которому маршрутизируются (rpc_url из конфигурации)
+    primary: String,
+    endpoints: Vec<Endpoint>,
+    current: usize,
+    last_probe: Option<Instant>,
+}
+
+// Маршрутизатор настраивается один раз при запуске
+static ROUTER: OnceLock<Mutex<Router>> = OnceLock::new();
+
+// Включение маршрутизации запросов к primary между primary и extra
+pub fn set_endpoints(primary: &str, extra: &[String]) {
+    let mut urls = vec![primary.to_string()];
+    urls.extend(extra.iter().filter(|url| *url != primary).cloned());
+    if urls.len() < 2 {
+        return;
+    }
+
+    let endpoints = urls
+        .into_iter()
+        .map(|url| Endpoint { url, latency_ms: None, unhealthy_until: None })
+        .collect();
+    let _ = ROUTER.set(Mutex::new(Router { primary: primary.to_string(), endpoints, current: 0, last_probe: None }));
+}
+
+// Эндпоинт для запроса к rpc_url; запросы к другим адресам не маршрутизируются
+pub fn route(rpc_url: &str) -> String {
+    let Some(router) = ROUTER.get() else {
+        return rpc_url.to_string();
+    };
+    let mut router = router.lock().unwrap();
+    if router.primary != rpc_url {
+        return rpc_url.to_string();
+    }
+
+    let now = Instant::now();
+    if router.last_probe.is_none_or(|last| now.duration_since(last) >= PROBE_INTERVAL) {
+        router.last_probe = Some(now);
+        let idle: Vec<String> = router
+            .endpoints
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != router.current)
+            .map(|(_, endpoint)| endpoint.url.clone())
+            .collect();
+        // Вне рантайма tokio (не бывает в этой программе) замеры просто не обновляются
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            for url in idle {
+                runtime.spawn(async move { rpc::probe(&url).await });
+            }
+        }
+    }
+
+    router.select(now);
+    router.endpoints[router.current].url.clone()
+}
+
+// Учет результата запроса к эндпоинту
+pub fn record(url: &str, elapsed: Duration, delivered: bool) {
+    let Some(router) = ROUTER.get() else {
+        return;
+    };
+    let mut router = router.lock().unwrap();
+    let Some(endpoint) = router.endpoints.iter_mut().find(|endpoint| endpoint.url == url) else {
+        return;
+    };
+
+    if delivered {
+        let sample = elapsed.as_secs_f64() * 1000.0;
+        endpoint.latency_ms = Some(match endpoint.latency_ms {
+            Some(average) => average + EWMA_WEIGHT * (sample - average),
+            None => sample,
+        });
+        endpoint.unhealthy_until = None;
+    } else {
+        endpoint.unhealthy_until = Some(Instant::now() + FAILURE_COOLDOWN);
+    }
+}
+
+impl Router {
+    // Переход на самый быстрый рабочий эндпоинт, если текущий не работает или заметно медленнее
+    fn select(&mut self, now: Instant) {
+        let best = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .filter(|(_, endpoint)| endpoint.healthy(now))
+            .min_by(|(_, a), (_, b)| {
+                let (a, b) = (a.latency_ms.unwrap_or(f64::MAX), b.latency_ms.unwrap_or(f64::MAX));
+                a.total_cmp(&b)
+            })
+            .map(|(i, _)| i);
+        let Some(best) = best else {
+            // Все эндпоинты с ошибками: остаемся на текущем
+            return;
+        };
+
+        let current = &self.endpoints[self.current];
+        let switch = match (current.healthy(now), current.latency_ms, self.endpoints[best].latency_ms) {
+            (false, _, _) => true,
+            (true, Some(current), Some(best)) => best < current * (1.0 - HYSTERESIS),
+            _ => false,
+        };
+        if switch {
+            self.current = best;
+        }
+    }
+}
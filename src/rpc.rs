@@ -0,0 +1,268 @@
+// src/rpc.rs
+// Общий JSON RPC вызов и разбор ответов Solana
+use anyhow::{Context as AnyhowContext, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Instant;
+
+use crate::chaos;
+use crate::debug_bundle;
+use crate::router;
+use crate::run_stats;
+use crate::usage;
+
+// Ошибка, возвращаемая RPC узлом в поле error
+#[derive(Debug, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+// Значение вместе со слотом, на котором оно было получено.
+// Слот отсутствует, если провайдер вернул результат без context
+#[derive(Debug)]
+pub struct WithContext<T> {
+    pub slot: Option<u64>,
+    pub value: T,
+}
+
+// Максимальная длина фрагмента ответа в сообщениях об ошибках
+const SNIPPET_LEN: usize = 200;
+
+// Заголовки ответа, полезные при разборе проблем провайдера
+const DIAGNOSTIC_HEADERS: &[&str] = &[
+    "content-type",
+    "retry-after",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+    "server",
+    "cf-ray",
+    "x-request-id",
+];
+
+// Выполнение JSON RPC вызова и разбор поля result в нужный тип
+pub async fn call<T: DeserializeOwned>(rpc_url: &str, method: &str, params: Value) -> Result<T> {
+    let result = call_raw(rpc_url, method, params).await?;
+    parse_result(method, result)
+}
+
+// Вызов именно этого эндпоинта в обход маршрутизации: проверка каждого из настроенных
+pub async fn call_direct<T: DeserializeOwned>(endpoint: &str, method: &str, params: Value) -> Result<T> {
+    let result = call_at(endpoint, method, params).await?;
+    parse_result(method, result)
+}
+
+fn parse_result<T: DeserializeOwned>(method: &str, result: Value) -> Result<T> {
+    serde_json::from_value(result.clone()).with_context(|| {
+        format!("Unexpected result shape for {}: {}", method, snippet(&result.to_string()))
+    })
+}
+
+// Вызов метода, результат которого обернут в { context, value }
+pub async fn call_with_context<T: DeserializeOwned>(
+    rpc_url: &str,
+    method: &str,
+    params: Value,
+) -> Result<WithContext<T>> {
+    let result = call_raw(rpc_url, method, params).await?;
+    parse_with_context(method, result)
+}
+
+// Разбор результата вида { context, value }
+pub fn parse_with_context<T: DeserializeOwned>(method: &str, result: Value) -> Result<WithContext<T>> {
+    // Некоторые провайдеры отдают голое значение без context
+    let (slot, value) = match result.get("value") {
+        Some(value) if result.is_object() => (
+            result.pointer("/context/slot").and_then(Value::as_u64),
+            value.clone(),
+        ),
+        _ => (None, result),
+    };
+
+    let value = serde_json::from_value(value.clone()).with_context(|| {
+        format!("Unexpected value shape for {}: {}", method, snippet(&value.to_string()))
+    })?;
+
+    Ok(WithContext { slot, value })
+}
+
+// Выполнение запроса и извлечение поля result без типизации.
+// Запрос к основному эндпоинту уходит на самый быстрый из настроенных (см. router)
+async fn call_raw(rpc_url: &str, method: &str, params: Value) -> Result<Value> {
+    call_at(&router::route(rpc_url), method, params).await
+}
+
+// Запрос к выбранному эндпоинту с учетом в статистике запуска и архиве для отчета об ошибке
+async fn call_at(endpoint: &str, method: &str, params: Value) -> Result<Value> {
+    let started = Instant::now();
+    let result = call_endpoint(endpoint, method, params, started).await;
+    debug_bundle::record(endpoint, method, started.elapsed(), result.as_ref().err());
+    run_stats::request(result.is_err());
+    result
+}
+
+async fn call_endpoint(endpoint: &str, method: &str, params: Value, started: Instant) -> Result<Value> {
+    let exchange = exchange(endpoint, method, params).await;
+
+    // Задержка учитывается только для ответов с HTTP 2xx; ошибки RPC уровня не делают узел нерабочим
+    let delivered = matches!(&exchange, Ok((status, _, _)) if status.is_success());
+    router::record(endpoint, started.elapsed(), delivered);
+
+    let (status, headers, body) = exchange?;
+    if !status.is_success() {
+        anyhow::bail!(
+            "HTTP {} for {}; headers: {}; body: {}",
+            status, method, headers, snippet(&body)
+        );
+    }
+
+    let envelope: Value = serde_json::from_str(&body).with_context(|| {
+        format!(
+            "Response to {} is not valid JSON (HTTP {}; headers: {}): {}",
+            method, status, headers, snippet(&body)
+        )
+    })?;
+
+    envelope_result(method, &envelope)
+}
+
+// Поле result ответа или ошибка из поля error
+fn envelope_result(method: &str, envelope: &Value) -> Result<Value> {
+    if let Some(error) = envelope.get("error").filter(|e| !e.is_null()) {
+        let error: RpcError = serde_json::from_value(error.clone())
+            .with_context(|| format!("Malformed error object for {}: {}", method, error))?;
+        anyhow::bail!("RPC error for {}: {} (code: {})", method, error.message, error.code);
+    }
+
+    match envelope.get("result") {
+        Some(result) => Ok(result.clone()),
+        None => anyhow::bail!("Response to {} has neither result nor error: {}", method, snippet(&envelope.to_string())),
+    }
+}
+
+// JSON-RPC batch: несколько вызовов одного метода одним HTTP запросом, результаты в порядке params.
+// Ошибка всего запроса - если провайдер не принимает batch; ошибки отдельных вызовов - в элементах
+pub async fn call_batch(rpc_url: &str, method: &str, params: Vec<Value>) -> Result<Vec<Result<Value>>> {
+    let endpoint = router::route(rpc_url);
+    let started = Instant::now();
+    let result = call_batch_endpoint(&endpoint, method, params, started).await;
+    debug_bundle::record(&endpoint, method, started.elapsed(), result.as_ref().err());
+    run_stats::request(result.is_err());
+    result
+}
+
+async fn call_batch_endpoint(endpoint: &str, method: &str, params: Vec<Value>, started: Instant) -> Result<Vec<Result<Value>>> {
+    let count = params.len();
+    let requests: Vec<Value> = params
+        .into_iter()
+        .enumerate()
+        .map(|(id, params)| json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+        .collect();
+    let exchange = send(endpoint, method, count, &Value::Array(requests)).await;
+
+    let delivered = matches!(&exchange, Ok((status, _, _)) if status.is_success());
+    router::record(endpoint, started.elapsed(), delivered);
+
+    let (status, headers, body) = exchange?;
+    if !status.is_success() {
+        anyhow::bail!(
+            "HTTP {} for {} batch; headers: {}; body: {}",
+            status, method, headers, snippet(&body)
+        );
+    }
+
+    // Провайдер без поддержки batch обычно отвечает одним объектом с ошибкой
+    let responses: Vec<Value> = serde_json::from_str(&body).with_context(|| {
+        format!("Response to {} batch is not a JSON array (HTTP {}): {}", method, status, snippet(&body))
+    })?;
+
+    let mut results: Vec<Option<Result<Value>>> = (0..count).map(|_| None).collect();
+    for response in responses {
+        let slot = response.get("id").and_then(Value::as_u64).and_then(|id| results.get_mut(id as usize));
+        if let Some(slot) = slot {
+            *slot = Some(envelope_result(method, &response));
+        }
+    }
+
+    Ok(results
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|| Err(anyhow::anyhow!("No response to {} in batch", method))))
+        .collect())
+}
+
+// Проверка доступности эндпоинта в обход маршрутизации
+pub async fn probe(rpc_url: &str) -> bool {
+    let started = Instant::now();
+    let healthy = matches!(
+        exchange(rpc_url, "getHealth", json!([])).await,
+        Ok((status, _, body)) if status.is_success() && body.contains("\"ok\"")
+    );
+    router::record(rpc_url, started.elapsed(), healthy);
+    healthy
+}
+
+// HTTP обмен: статус, диагностические заголовки и тело ответа
+async fn exchange(rpc_url: &str, method: &str, params: Value) -> Result<(reqwest::StatusCode, String, String)> {
+    let request_body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params
+    });
+
+    send(rpc_url, method, 1, &request_body).await
+}
+
+// Отправка тела запроса; calls - число вызовов в нем, провайдер списывает кредиты за каждый
+async fn send(rpc_url: &str, method: &str, calls: usize, request_body: &Value) -> Result<(reqwest::StatusCode, String, String)> {
+    chaos::before_call(method).await?;
+    for _ in 0..calls {
+        usage::charge(rpc_url, method)?;
+    }
+    let client = reqwest::Client::new();
+
+    let payload = serde_json::to_string(request_body)?;
+    let sent = payload.len();
+    let response = client.post(rpc_url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload)
+        .send()
+        .await
+        .with_context(|| format!("Failed to send {} request", method))?;
+
+    let status = response.status();
+    let headers = diagnostic_headers(response.headers());
+
+    let body = response.text().await
+        .with_context(|| format!("Failed to read {} response body (HTTP {})", method, status))?;
+    run_stats::transferred(sent, body.len());
+
+    Ok((status, headers, body))
+}
+
+// Отбор заголовков ответа для диагностики в виде "name=value, ..."
+fn diagnostic_headers(headers: &reqwest::header::HeaderMap) -> String {
+    let found: Vec<String> = DIAGNOSTIC_HEADERS
+        .iter()
+        .filter_map(|name| {
+            let value = headers.get(*name)?.to_str().ok()?;
+            Some(format!("{}={}", name, value))
+        })
+        .collect();
+
+    if found.is_empty() {
+        "none".to_string()
+    } else {
+        found.join(", ")
+    }
+}
+
+// Усечение текста ответа для сообщений об ошибках
+fn snippet(text: &str) -> String {
+    match text.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
@@ -0,0 +1,92 @@
+// src/run_stats.rs
+// Ресурсы, затраченные запуском: время, RPC запросы, трафик, повторы, пиковая память
+use anyhow::{Context as AnyhowContext, Result};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+static STARTED: OnceLock<Instant> = OnceLock::new();
+static REQUESTS: AtomicU64 = AtomicU64::new(0);
+static FAILED: AtomicU64 = AtomicU64::new(0);
+static BYTES_SENT: AtomicU64 = AtomicU64::new(0);
+static BYTES_RECEIVED: AtomicU64 = AtomicU64::new(0);
+static RETRIES: AtomicU64 = AtomicU64::new(0);
+
+// Начало отсчета времени запуска; вызывается в начале main
+pub fn start() {
+    let _ = STARTED.set(Instant::now());
+}
+
+// Завершенный RPC запрос
+pub fn request(failed: bool) {
+    REQUESTS.fetch_add(1, Ordering::Relaxed);
+    if failed {
+        FAILED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// Объем тела запроса и ответа
+pub fn transferred(sent: usize, received: usize) {
+    BYTES_SENT.fetch_add(sent as u64, Ordering::Relaxed);
+    BYTES_RECEIVED.fetch_add(received as u64, Ordering::Relaxed);
+}
+
+// Повтор запроса после ошибки
+pub fn retry() {
+    RETRIES.fetch_add(1, Ordering::Relaxed);
+}
+
+// Пиковый объем памяти процесса в КБ (VmHWM); только Linux
+fn peak_memory_kb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+fn wall_seconds() -> f64 {
+    STARTED.get().map_or(0.0, |started| started.elapsed().as_secs_f64())
+}
+
+// Итог запуска в stdout
+pub fn print() {
+    let requests = REQUESTS.load(Ordering::Relaxed);
+    println!(
+        "Run stats: {:.2}s wall time, {} RPC requests ({} failed, {} retries), {:.1} KB sent, {:.1} KB received, peak memory {}",
+        wall_seconds(),
+        requests,
+        FAILED.load(Ordering::Relaxed),
+        RETRIES.load(Ordering::Relaxed),
+        BYTES_SENT.load(Ordering::Relaxed) as f64 / 1024.0,
+        BYTES_RECEIVED.load(Ordering::Relaxed) as f64 / 1024.0,
+        peak_memory_kb().map_or_else(|| "unavailable".to_string(), |kb| format!("{} MB", kb / 1024)),
+    );
+}
+
+// Итог запуска в текстовом формате Prometheus (для textfile collector node_exporter)
+pub fn write_metrics(path: &Path, command: &str) -> Result<()> {
+    // Значения одного запуска, поэтому все метрики - gauge
+    let mut metrics = vec![
+        ("wall_seconds", format!("{:.3}", wall_seconds())),
+        ("rpc_requests", REQUESTS.load(Ordering::Relaxed).to_string()),
+        ("rpc_failed_requests", FAILED.load(Ordering::Relaxed).to_string()),
+        ("rpc_retries", RETRIES.load(Ordering::Relaxed).to_string()),
+        ("rpc_sent_bytes", BYTES_SENT.load(Ordering::Relaxed).to_string()),
+        ("rpc_received_bytes", BYTES_RECEIVED.load(Ordering::Relaxed).to_string()),
+    ];
+    if let Some(kb) = peak_memory_kb() {
+        metrics.push(("peak_memory_bytes", (kb * 1024).to_string()));
+    }
+
+    let mut text = String::new();
+    for (name, value) in metrics {
+        text.push_str(&format!("# TYPE solana_balances_run_{} gauge\n", name));
+        text.push_str(&format!("solana_balances_run_{}{{command=\"{}\"}} {}\n", name, command, value));
+    }
+
+    // Коллектор не должен прочитать недописанный файл
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, text).with_context(|| format!("Failed to write metrics to {:?}", temp))?;
+    fs::rename(&temp, path).with_context(|| format!("Failed to replace {:?}", path))
+}
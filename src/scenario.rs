@@ -0,0 +1,143 @@
+// src/scenario.rs
+// Переоценка текущих балансов по гипотетическим ценам из файла сценариев
+use anyhow::{Context as AnyhowContext, Result};
+use futures::future::join_all;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+
+use crate::format;
+use crate::rpc;
+use crate::token;
+
+// Ключ цены SOL; остальные ключи - mint токенов
+const SOL_KEY: &str = "SOL";
+
+// Файл сценариев: базовые цены в USD и их изменения
+#[derive(Debug, Deserialize)]
+pub struct ScenarioFile {
+    pub prices: HashMap<String, f64>,
+    pub scenarios: Vec<Scenario>,
+}
+
+// Сценарий: новые цены и/или изменения базовых цен в процентах
+#[derive(Debug, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub prices: HashMap<String, f64>,
+    #[serde(default)]
+    pub changes: HashMap<String, f64>,
+}
+
+// Активы кошелька: SOL в лампортах и токены в единицах токена по mint
+struct Holdings {
+    wallet: String,
+    assets: HashMap<String, f64>,
+}
+
+pub fn load(path: &Path) -> Result<ScenarioFile> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open scenario file: {:?}", path))?;
+
+    let scenarios: ScenarioFile = serde_yaml::from_reader(file)
+        .with_context(|| "Failed to parse scenario file")?;
+    for scenario in &scenarios.scenarios {
+        if let Some(asset) = scenario.changes.keys().find(|asset| !scenarios.prices.contains_key(*asset)) {
+            anyhow::bail!("Scenario '{}' changes {} which has no base price", scenario.name, asset);
+        }
+    }
+
+    Ok(scenarios)
+}
+
+impl Scenario {
+    // Цены сценария: базовые, затем изменения в процентах, затем явно заданные цены
+    fn apply(&self, base: &HashMap<String, f64>) -> HashMap<String, f64> {
+        let mut prices = base.clone();
+        for (asset, percent) in &self.changes {
+            if let Some(price) = prices.get_mut(asset) {
+                *price *= 1.0 + percent / 100.0;
+            }
+        }
+        prices.extend(self.prices.iter().map(|(asset, price)| (asset.clone(), *price)));
+        prices
+    }
+}
+
+// Стресс-тест: стоимость портфеля по базовым ценам и в каждом сценарии
+pub async fn stress_report(rpc_url: &str, wallets: &[String], file: &ScenarioFile) -> Result<()> {
+    let results = join_all(wallets.iter().map(|wallet| get_holdings(rpc_url, wallet))).await;
+    let mut holdings = Vec::new();
+    for (wallet, result) in wallets.iter().zip(results) {
+        match result {
+            Ok(wallet_holdings) => holdings.push(wallet_holdings),
+            Err(e) => println!("Error fetching balances for wallet {}: {:#}", wallet, e),
+        }
+    }
+
+    // Токены без цены не оцениваются ни в одном сценарии
+    let mut unpriced: Vec<&String> = holdings
+        .iter()
+        .flat_map(|h| h.assets.keys())
+        .filter(|asset| !file.prices.contains_key(*asset) && !file.scenarios.iter().any(|s| s.prices.contains_key(*asset)))
+        .collect();
+    unpriced.sort();
+    unpriced.dedup();
+
+    let base_total = print_valuation("Base prices", &holdings, &file.prices, None);
+    for scenario in &file.scenarios {
+        print_valuation(&scenario.name, &holdings, &scenario.apply(&file.prices), Some(base_total));
+    }
+
+    if !unpriced.is_empty() {
+        println!("Not valued (no price): {}", unpriced.iter().map(|asset| asset.as_str()).collect::<Vec<_>>().join(", "));
+    }
+
+    Ok(())
+}
+
+// Вывод стоимости по кошелькам и итога; возвращает итог
+fn print_valuation(name: &str, holdings: &[Holdings], prices: &HashMap<String, f64>, base: Option<f64>) -> f64 {
+    println!("{}:", name);
+    let mut total = 0.0;
+    for wallet in holdings {
+        let value: f64 = wallet.assets
+            .iter()
+            .filter_map(|(asset, amount)| Some(amount * prices.get(asset)?))
+            .sum();
+        total += value;
+        println!("  {}: {}", wallet.wallet, format::usd(value));
+    }
+
+    match base {
+        Some(base) if base != 0.0 => println!(
+            "  Total: {} ({} / {:+.1}%)",
+            format::usd(total),
+            format::usd(total - base),
+            (total - base) * 100.0 / base
+        ),
+        Some(base) => println!("  Total: {} ({})", format::usd(total), format::usd(total - base)),
+        None => println!("  Total: {}", format::usd(total)),
+    }
+    total
+}
+
+// SOL и ненулевые токен-балансы кошелька
+async fn get_holdings(rpc_url: &str, wallet: &str) -> Result<Holdings> {
+    let balance: rpc::WithContext<u64> = rpc::call_with_context(rpc_url, "getBalance", json!([wallet]))
+        .await
+        .with_context(|| format!("Failed to fetch balance for wallet: {}", wallet))?;
+
+    let mut assets = HashMap::new();
+    assets.insert(SOL_KEY.to_string(), balance.value as f64 / 1e9);
+    for account in token::get_token_accounts(rpc_url, wallet).await? {
+        if account.amount > 0 {
+            *assets.entry(account.mint).or_insert(0.0) += account.amount as f64 / 10f64.powi(account.decimals as i32);
+        }
+    }
+
+    Ok(Holdings { wallet: wallet.to_string(), assets })
+}
@@ -0,0 +1,129 @@
+// src/security.rs
+// Проверка состояния безопасности отслеживаемых кошельков
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+use crate::account;
+use crate::format;
+use crate::stake;
+use crate::token;
+use crate::tx;
+
+// Изменения стейк-аккаунта за этот период считаются недавними
+const RECENT_CHANGE_DAYS: i64 = 7;
+
+// Результаты проверок одного кошелька
+struct Findings {
+    warnings: usize,
+}
+
+impl Findings {
+    fn warn(&mut self, message: String) {
+        self.warnings += 1;
+        println!("  [WARN] {}", message);
+    }
+
+    fn ok(&self, message: String) {
+        println!("  [OK] {}", message);
+    }
+
+    fn info(&self, message: String) {
+        println!("  [INFO] {}", message);
+    }
+}
+
+// Отчет по всем кошелькам; при наличии предупреждений запуск завершается ошибкой
+pub async fn run_report(rpc_url: &str, wallets: &[String]) -> Result<()> {
+    let mut total_warnings = 0;
+
+    for wallet in wallets {
+        println!("Security report for {}:", wallet);
+        let mut findings = Findings { warnings: 0 };
+
+        if let Err(e) = check_wallet(rpc_url, wallet, &mut findings).await {
+            findings.warn(format!("check incomplete: {:#}", e));
+        }
+        total_warnings += findings.warnings;
+    }
+
+    if total_warnings > 0 {
+        anyhow::bail!("Security report found {} warnings", total_warnings);
+    }
+    println!("Security report: no warnings");
+
+    Ok(())
+}
+
+// Все проверки одного кошелька
+async fn check_wallet(rpc_url: &str, wallet: &str, findings: &mut Findings) -> Result<()> {
+    // Владелец аккаунта: обычный кошелек принадлежит System Program
+    match account::get_account_info(rpc_url, wallet).await? {
+        None => findings.info("account does not exist on chain".to_string()),
+        Some(info) if info.executable => findings.warn("account is an executable program".to_string()),
+        Some(info) if info.owner != account::SYSTEM_PROGRAM => findings.warn(format!(
+            "balance is held on an account owned by program {} (PDA or program account), not a system account",
+            info.owner
+        )),
+        Some(_) => findings.ok("system-owned account".to_string()),
+    }
+
+    // Делегаты и полномочия закрытия токен-аккаунтов
+    let token_accounts = token::get_token_accounts(rpc_url, wallet).await?;
+    let mut token_issues = 0;
+    for token_account in &token_accounts {
+        if let Some(delegate) = &token_account.delegate {
+            token_issues += 1;
+            findings.warn(format!(
+                "token account {} (mint {}) has active delegate {} approved for {} base units",
+                token_account.address, token_account.mint, delegate, token_account.delegated_amount
+            ));
+        }
+        if let Some(authority) = token_account.close_authority.as_ref().filter(|a| *a != wallet) {
+            token_issues += 1;
+            findings.warn(format!(
+                "token account {} (mint {}) can be closed by {}",
+                token_account.address, token_account.mint, authority
+            ));
+        }
+        if token_account.state == "frozen" {
+            findings.info(format!("token account {} (mint {}) is frozen", token_account.address, token_account.mint));
+        }
+    }
+    if token_issues == 0 {
+        findings.ok(format!("{} token accounts, no delegates or foreign close authorities", token_accounts.len()));
+    }
+
+    // Полномочия стейк-аккаунтов и их недавние изменения
+    let stake_accounts = stake::get_stake_accounts(rpc_url, wallet).await?;
+    for stake_account in &stake_accounts {
+        findings.info(format!(
+            "stake account {}: {} SOL, delegated to {}",
+            stake_account.address,
+            format::sol(stake_account.lamports),
+            stake_account.voter.as_deref().unwrap_or("nobody")
+        ));
+        if stake_account.withdrawer != wallet {
+            findings.warn(format!(
+                "stake account {} withdrawer is {} (wallet is staker only)",
+                stake_account.address, stake_account.withdrawer
+            ));
+        } else if stake_account.staker != wallet {
+            findings.warn(format!(
+                "stake account {} staker is {} (wallet is withdrawer only)",
+                stake_account.address, stake_account.staker
+            ));
+        }
+
+        let last = tx::get_signatures(rpc_url, &stake_account.address, 1).await?;
+        let changed_at = last.first().and_then(|info| info.block_time);
+        let recent = (Utc::now() - Duration::days(RECENT_CHANGE_DAYS)).timestamp();
+        if let Some(changed_at) = changed_at.filter(|time| *time > recent) {
+            findings.warn(format!(
+                "stake account {} was modified recently ({}); review its authorities",
+                stake_account.address, format::time(changed_at)
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// src/signing.rs
+// Общий порядок выдачи транзакций: неподписанные для офлайн-подписи,
+// подписанные локальным ключом или сразу отправленные в сеть
+use anyhow::Result;
+use std::path::Path;
+
+use crate::transaction::{self, Instruction, Pubkey};
+
+// Как поступить с собранными транзакциями
+pub struct SigningOptions<'a> {
+    pub keypair: Option<&'a Path>,
+    pub submit: bool,
+}
+
+// Сборка транзакций от имени владельца и их подпись/отправка согласно опциям
+pub async fn deliver(
+    rpc_url: &str,
+    owner: &str,
+    instructions: Vec<Instruction>,
+    options: &SigningOptions<'_>,
+) -> Result<()> {
+    let payer: Pubkey = transaction::decode_pubkey(owner)?;
+
+    let keypair = options.keypair.map(transaction::load_keypair).transpose()?;
+    if let Some(keypair) = &keypair {
+        let signer = transaction::encode_pubkey(&keypair.verifying_key().to_bytes());
+        if signer != owner {
+            anyhow::bail!("Keypair belongs to {}, but the accounts are owned by {}", signer, owner);
+        }
+    }
+
+    let blockhash = transaction::latest_blockhash(rpc_url).await?;
+    let mut transactions = transaction::pack(&payer, instructions, &blockhash)?;
+    let total = transactions.len();
+
+    for (i, tx) in transactions.iter_mut().enumerate() {
+        let Some(keypair) = &keypair else {
+            println!("Unsigned transaction {}/{} (base64):", i + 1, total);
+            println!("{}", tx.to_base64());
+            continue;
+        };
+
+        tx.sign(keypair);
+        if options.submit {
+            let signature = transaction::send(rpc_url, tx).await?;
+            println!("Submitted transaction {}/{}: {}", i + 1, total, signature);
+        } else {
+            println!("Signed transaction {}/{} {} (base64):", i + 1, total, tx.signature_base58());
+            println!("{}", tx.to_base64());
+        }
+    }
+
+    Ok(())
+}
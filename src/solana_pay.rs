@@ -0,0 +1,196 @@
+// src/solana_pay.rs
+// Разбор ссылок Solana Pay (transfer request) и ожидание оплаты по reference
+use anyhow::{Context as AnyhowContext, Result};
+use reqwest::Url;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+use crate::tx;
+
+// Количество знаков после запятой у SOL
+pub const SOL_DECIMALS: u32 = 9;
+
+// Запрос на перевод, извлеченный из ссылки solana:
+#[derive(Debug)]
+pub struct TransferRequest {
+    pub recipient: String,
+    // Сумма в виде строки, как она указана в ссылке
+    pub amount: Option<String>,
+    pub spl_token: Option<String>,
+    pub references: Vec<String>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub memo: Option<String>,
+}
+
+// Разбор ссылки вида solana:<recipient>?amount=..&spl-token=..&reference=..
+pub fn parse_url(input: &str) -> Result<TransferRequest> {
+    let url = Url::parse(input.trim())
+        .with_context(|| format!("Invalid Solana Pay URL: {}", input))?;
+
+    if url.scheme() != "solana" {
+        anyhow::bail!("Unsupported URL scheme '{}', expected 'solana'", url.scheme());
+    }
+
+    let recipient = url.path().to_string();
+    if recipient.starts_with("http") {
+        anyhow::bail!("Transaction request URLs are not supported, only transfer requests");
+    }
+    if recipient.is_empty() {
+        anyhow::bail!("Solana Pay URL has no recipient");
+    }
+
+    let mut request = TransferRequest {
+        recipient,
+        amount: None,
+        spl_token: None,
+        references: Vec::new(),
+        label: None,
+        message: None,
+        memo: None,
+    };
+
+    for (key, value) in url.query_pairs() {
+        let value = value.into_owned();
+        match key.as_ref() {
+            "amount" => request.amount = Some(value),
+            "spl-token" => request.spl_token = Some(value),
+            "reference" => request.references.push(value),
+            "label" => request.label = Some(value),
+            "message" => request.message = Some(value),
+            "memo" => request.memo = Some(value),
+            _ => {}
+        }
+    }
+
+    // Проверяем сумму сразу, чтобы не ждать оплату по неверной ссылке
+    if let Some(amount) = &request.amount {
+        if request.spl_token.is_none() {
+            parse_decimal_amount(amount, SOL_DECIMALS)?;
+        }
+    }
+
+    Ok(request)
+}
+
+// Перевод десятичной суммы в минимальные единицы без потери точности
+pub fn parse_decimal_amount(amount: &str, decimals: u32) -> Result<u64> {
+    let invalid = || format!("Invalid amount: {}", amount);
+
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        anyhow::bail!(invalid());
+    }
+    if fraction.len() > decimals as usize {
+        anyhow::bail!("Amount {} has more than {} decimal places", amount, decimals);
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        anyhow::bail!(invalid());
+    }
+
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    digits.parse::<u64>().with_context(invalid)
+}
+
+// Ожидание транзакции с reference и проверка суммы перевода
+pub async fn watch_payment(
+    rpc_url: &str,
+    request: &TransferRequest,
+    interval: Duration,
+    timeout: Duration,
+) -> Result<()> {
+    let reference = request.references.first()
+        .context("Solana Pay URL has no reference, payment cannot be monitored")?;
+
+    println!("Waiting for reference {} (timeout {}s)", reference, timeout.as_secs());
+    let started = Instant::now();
+
+    let found = loop {
+        let signatures = tx::get_signatures(rpc_url, reference, 1).await?;
+
+        if let Some(found) = signatures.into_iter().next() {
+            break found;
+        }
+        if started.elapsed() >= timeout {
+            anyhow::bail!("Payment not found within {}s", timeout.as_secs());
+        }
+        tokio::time::sleep(interval).await;
+    };
+
+    println!("Payment found: {} (slot {})", found.signature, found.slot);
+    if let Some(err) = &found.err {
+        anyhow::bail!("Payment transaction {} failed: {}", found.signature, err);
+    }
+
+    let transaction = tx::get_transaction(rpc_url, &found.signature).await?;
+
+    verify_amount(request, &transaction)
+}
+
+// Сверка полученной получателем суммы с суммой из ссылки
+fn verify_amount(request: &TransferRequest, transaction: &Value) -> Result<()> {
+    let Some(amount) = &request.amount else {
+        println!("No amount requested, any transfer is accepted");
+        return Ok(());
+    };
+
+    let (received, expected) = received_and_expected(
+        transaction,
+        &request.recipient,
+        amount,
+        request.spl_token.as_deref(),
+    )?;
+
+    if !amount_accepted(received, expected) {
+        anyhow::bail!(
+            "Payment amount mismatch: expected {} base units, recipient received {}",
+            expected, received
+        );
+    }
+    println!("Payment confirmed: recipient received {} base units (expected {})", received, expected);
+
+    Ok(())
+}
+
+// Общее правило для pay и watch-deposits: переплата принимается, недоплата - нет
+pub fn amount_accepted(received: u64, expected: u64) -> bool {
+    received >= expected
+}
+
+// Полученная адресом сумма и ожидаемая сумма в минимальных единицах
+pub fn received_and_expected(
+    transaction: &Value,
+    recipient: &str,
+    amount: &str,
+    spl_token: Option<&str>,
+) -> Result<(u64, u64)> {
+    match spl_token {
+        None => Ok((
+            tx::sol_received(transaction, recipient)?,
+            parse_decimal_amount(amount, SOL_DECIMALS)?,
+        )),
+        Some(mint) => {
+            let (received, decimals) = tx::token_received(transaction, recipient, mint)?;
+            Ok((received, parse_decimal_amount(amount, decimals)?))
+        }
+    }
+}
+
+// Вывод содержимого запроса на перевод
+pub fn print_request(request: &TransferRequest) {
+    println!("Payment request:");
+    println!("  Recipient: {}", request.recipient);
+    match (&request.amount, &request.spl_token) {
+        (Some(amount), Some(mint)) => println!("  Amount: {} (token {})", amount, mint),
+        (Some(amount), None) => println!("  Amount: {} SOL", amount),
+        (None, _) => println!("  Amount: not specified"),
+    }
+    for reference in &request.references {
+        println!("  Reference: {}", reference);
+    }
+    for (name, value) in [("Label", &request.label), ("Message", &request.message), ("Memo", &request.memo)] {
+        if let Some(value) = value {
+            println!("  {}: {}", name, value);
+        }
+    }
+}
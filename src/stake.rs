@@ -0,0 +1,77 @@
+// src/stake.rs
+// Стейк-аккаунты, где кошелек является staker или withdrawer
+use anyhow::{Context as AnyhowContext, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::account::{self, KeyedAccount};
+use crate::rpc;
+
+// Смещения полей authorized.staker и authorized.withdrawer в данных стейк-аккаунта
+const STAKER_OFFSET: u64 = 12;
+const WITHDRAWER_OFFSET: u64 = 44;
+
+// Стейк-аккаунт с полномочиями
+#[derive(Debug)]
+pub struct StakeAccount {
+    pub address: String,
+    pub lamports: u64,
+    pub staker: String,
+    pub withdrawer: String,
+    // Валидатор, если стейк делегирован
+    pub voter: Option<String>,
+}
+
+// Стейк-аккаунты, в которых кошелек указан как staker или withdrawer
+pub async fn get_stake_accounts(rpc_url: &str, wallet: &str) -> Result<Vec<StakeAccount>> {
+    let mut found: HashMap<String, StakeAccount> = HashMap::new();
+
+    for offset in [STAKER_OFFSET, WITHDRAWER_OFFSET] {
+        let accounts: Vec<KeyedAccount> = rpc::call(
+            rpc_url,
+            "getProgramAccounts",
+            json!([account::STAKE_PROGRAM, {
+                "encoding": "jsonParsed",
+                "filters": [{ "memcmp": { "offset": offset, "bytes": wallet } }]
+            }]),
+        )
+        .await
+        .with_context(|| format!("Failed to fetch stake accounts of {}", wallet))?;
+
+        for keyed in accounts {
+            if let Some(stake) = parse_stake_account(&keyed) {
+                found.insert(stake.address.clone(), stake);
+            }
+        }
+    }
+
+    let mut accounts: Vec<StakeAccount> = found.into_values().collect();
+    accounts.sort_by(|a, b| a.address.cmp(&b.address));
+    Ok(accounts)
+}
+
+// Стейк-аккаунт по адресу; None, если аккаунт не найден или не принадлежит Stake Program
+pub async fn get_stake_account(rpc_url: &str, address: &str) -> Result<Option<StakeAccount>> {
+    let Some(account) = account::get_account_info(rpc_url, address).await? else {
+        return Ok(None);
+    };
+    if account.owner != account::STAKE_PROGRAM {
+        return Ok(None);
+    }
+
+    Ok(parse_stake_account(&KeyedAccount { pubkey: address.to_string(), account }))
+}
+
+// Разбор jsonParsed данных стейк-аккаунта
+fn parse_stake_account(keyed: &KeyedAccount) -> Option<StakeAccount> {
+    let info = account::parsed_info(&keyed.account)?;
+    let text = |pointer: &str| info.pointer(pointer).and_then(Value::as_str).map(str::to_string);
+
+    Some(StakeAccount {
+        address: keyed.pubkey.clone(),
+        lamports: keyed.account.lamports,
+        staker: text("/meta/authorized/staker")?,
+        withdrawer: text("/meta/authorized/withdrawer")?,
+        voter: text("/stake/delegation/voter"),
+    })
+}
@@ -0,0 +1,80 @@
+// src/stake_audit.rs
+// Сверка полномочий стейк-аккаунтов со списком разрешенных ключей
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::format;
+use crate::stake::{self, StakeAccount};
+
+// Настройки проверки из секции stake_audit конфигурации
+#[derive(Debug, Default, Deserialize)]
+pub struct StakeAuditConfig {
+    // Стейк-аккаунты, проверяемые всегда. Аккаунт со смененными полномочиями
+    // больше не находится по адресу кошелька, поэтому важные аккаунты лучше перечислить явно
+    #[serde(default)]
+    pub accounts: Vec<String>,
+    // Разрешенные staker/withdrawer; по умолчанию отслеживаемые кошельки
+    pub allowed_stakers: Option<Vec<String>>,
+    pub allowed_withdrawers: Option<Vec<String>>,
+}
+
+// Проверка всех найденных и явно указанных стейк-аккаунтов
+pub async fn run(rpc_url: &str, wallets: &[String], audit: &StakeAuditConfig) -> Result<()> {
+    let mut accounts: BTreeMap<String, StakeAccount> = BTreeMap::new();
+    let mut alerts = 0;
+
+    for wallet in wallets {
+        match stake::get_stake_accounts(rpc_url, wallet).await {
+            Ok(found) => accounts.extend(found.into_iter().map(|a| (a.address.clone(), a))),
+            Err(e) => {
+                alerts += 1;
+                println!("[ALERT] failed to list stake accounts of {}: {:#}", wallet, e);
+            }
+        }
+    }
+    for address in &audit.accounts {
+        match stake::get_stake_account(rpc_url, address).await {
+            Ok(Some(account)) => {
+                accounts.insert(account.address.clone(), account);
+            }
+            Ok(None) => {
+                alerts += 1;
+                println!("[ALERT] {} is not a stake account (closed or reassigned?)", address);
+            }
+            Err(e) => {
+                alerts += 1;
+                println!("[ALERT] failed to fetch stake account {}: {:#}", address, e);
+            }
+        }
+    }
+
+    let allowed_stakers = audit.allowed_stakers.as_deref().unwrap_or(wallets);
+    let allowed_withdrawers = audit.allowed_withdrawers.as_deref().unwrap_or(wallets);
+
+    println!("Stake authority audit for {} accounts:", accounts.len());
+    for account in accounts.values() {
+        println!(
+            "{}: {} SOL, staker {}, withdrawer {}",
+            account.address,
+            format::sol(account.lamports),
+            account.staker,
+            account.withdrawer
+        );
+        if !allowed_stakers.contains(&account.staker) {
+            alerts += 1;
+            println!("[ALERT] {} staker {} is not in the allowlist", account.address, account.staker);
+        }
+        if !allowed_withdrawers.contains(&account.withdrawer) {
+            alerts += 1;
+            println!("[ALERT] {} withdrawer {} is not in the allowlist", account.address, account.withdrawer);
+        }
+    }
+
+    if alerts > 0 {
+        anyhow::bail!("Stake authority audit raised {} alerts", alerts);
+    }
+    println!("Stake authority audit passed");
+
+    Ok(())
+}
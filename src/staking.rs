@@ -0,0 +1,148 @@
+// src/staking.rs
+// Свободные и застейканные SOL кошельков: нативный стейк, токены ликвидного стейкинга, ордера на DEX
+use anyhow::{Context as AnyhowContext, Result};
+use futures::future::join_all;
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::dex;
+use crate::format;
+use crate::lst;
+use crate::rpc;
+use crate::stake;
+use crate::token;
+
+// Активы кошелька в SOL-эквиваленте, лампорты
+#[derive(Debug, Default)]
+pub struct StakingBreakdown {
+    pub liquid: u64,
+    pub native_stake: u64,
+    pub liquid_staking: u64,
+    // Wrapped SOL в открытых ордерах на DEX
+    pub dex_locked: u64,
+    pub holdings: Vec<LstHolding>,
+    // Все токен-аккаунты кошелька, чтобы вызывающий код не запрашивал их повторно
+    pub token_accounts: Vec<token::TokenAccount>,
+}
+
+// Баланс токена ликвидного стейкинга и его стоимость по курсу пула
+#[derive(Debug)]
+pub struct LstHolding {
+    pub token: &'static lst::LiquidStakingToken,
+    pub amount: u64,
+    pub decimals: u32,
+    pub rate: lst::ExchangeRate,
+    pub lamports: u64,
+}
+
+impl StakingBreakdown {
+    pub fn staked(&self) -> u64 {
+        self.native_stake + self.liquid_staking
+    }
+}
+
+// Отчет по всем кошелькам
+pub async fn run_report(rpc_url: &str, wallets: &[String]) -> Result<()> {
+    let rates = get_rates(rpc_url).await;
+
+    println!("Staking for {} wallets:", wallets.len());
+    let mut total = StakingBreakdown::default();
+    for wallet in wallets {
+        match wallet_breakdown(rpc_url, wallet, &rates).await {
+            Ok(breakdown) => {
+                total.liquid += breakdown.liquid;
+                total.native_stake += breakdown.native_stake;
+                total.liquid_staking += breakdown.liquid_staking;
+                total.dex_locked += breakdown.dex_locked;
+                print_breakdown(wallet, &breakdown);
+            }
+            Err(e) => println!("Error fetching balances for wallet {}: {:#}", wallet, e),
+        }
+    }
+    print_breakdown("Total", &total);
+
+    Ok(())
+}
+
+fn print_breakdown(name: &str, breakdown: &StakingBreakdown) {
+    println!(
+        "{}: liquid {} SOL, staked {} SOL (native {} SOL, liquid staking {} SOL), locked on DEX {} SOL",
+        name,
+        format::sol(breakdown.liquid),
+        format::sol(breakdown.staked()),
+        format::sol(breakdown.native_stake),
+        format::sol(breakdown.liquid_staking),
+        format::sol(breakdown.dex_locked)
+    );
+    for holding in &breakdown.holdings {
+        println!(
+            "  {} {} at {:.6} SOL = {} SOL",
+            format::units(holding.amount, holding.decimals),
+            holding.token.symbol,
+            holding.rate.as_f64(),
+            format::sol(holding.lamports)
+        );
+    }
+}
+
+// Курсы всех известных токенов; токены с недоступным курсом пропускаются с сообщением
+pub async fn get_rates(rpc_url: &str) -> HashMap<&'static str, lst::ExchangeRate> {
+    let results = join_all(lst::TOKENS.iter().map(|token| lst::exchange_rate(rpc_url, token))).await;
+
+    let mut rates = HashMap::new();
+    for (token, result) in lst::TOKENS.iter().zip(results) {
+        match result {
+            Ok(rate) => {
+                rates.insert(token.mint, rate);
+            }
+            Err(e) => println!("Error fetching {} exchange rate, it is not counted: {:#}", token.symbol, e),
+        }
+    }
+    rates
+}
+
+// Свободный баланс, стейк-аккаунты и токены ликвидного стейкинга кошелька
+pub async fn wallet_breakdown(
+    rpc_url: &str,
+    wallet: &str,
+    rates: &HashMap<&'static str, lst::ExchangeRate>,
+) -> Result<StakingBreakdown> {
+    let balance: rpc::WithContext<u64> = rpc::call_with_context(rpc_url, "getBalance", json!([wallet]))
+        .await
+        .with_context(|| format!("Failed to fetch balance for wallet: {}", wallet))?;
+
+    let native_stake = stake::get_stake_accounts(rpc_url, wallet)
+        .await?
+        .iter()
+        .map(|account| account.lamports)
+        .sum();
+
+    let token_accounts = token::get_token_accounts(rpc_url, wallet).await?;
+    let mut holdings = Vec::new();
+    for account in &token_accounts {
+        let (Some(token), Some(rate)) = (lst::find(&account.mint), rates.get(account.mint.as_str())) else {
+            continue;
+        };
+        if account.amount == 0 {
+            continue;
+        }
+        holdings.push(LstHolding {
+            token,
+            amount: account.amount,
+            decimals: account.decimals,
+            rate: *rate,
+            lamports: rate.to_lamports(account.amount),
+        });
+    }
+
+    let dex_locked = dex::locked_lamports(&dex::get_open_orders(rpc_url, wallet).await?);
+
+    Ok(StakingBreakdown {
+        liquid: balance.value,
+        native_stake,
+        liquid_staking: holdings.iter().map(|holding| holding.lamports).sum(),
+        dex_locked,
+        holdings,
+        token_accounts,
+    })
+}
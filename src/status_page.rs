@@ -0,0 +1,355 @@
+// src/status_page.rs
+// Статическая публичная страница (HTML + JSON) с балансами явно выбранных кошельков
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::components::{self, Components};
+use crate::format::{self, Redact};
+use crate::groups::{self, GroupConfig};
+use crate::{get_wallet_balances, WalletBalance};
+
+// Секция status_page конфигурации; публикуются только перечисленные здесь кошельки
+#[derive(Debug, Deserialize)]
+pub struct StatusPageConfig {
+    #[serde(default = "default_title")]
+    pub title: String,
+    // Каталог для index.html и status.json
+    #[serde(default = "default_output")]
+    pub output: PathBuf,
+    #[serde(default)]
+    pub wallets: Vec<PublicWallet>,
+    // Группа из секции groups, кошельки которой публикуются без меток
+    pub group: Option<String>,
+    // Скрыть адреса: на странице остаются только метки и балансы
+    #[serde(default)]
+    pub hide_addresses: bool,
+    // Округленные итоги по группам; отдельные кошельки групп при этом не публикуются
+    #[serde(default)]
+    pub aggregates: Vec<AggregateConfig>,
+    // Публиковать составляющие баланса (свободные, застейканные, заблокированные SOL)
+    #[serde(default)]
+    pub components: bool,
+}
+
+// Итог группы из секции groups
+#[derive(Debug, Deserialize)]
+pub struct AggregateConfig {
+    pub group: String,
+    // Шаг округления итога в SOL: движения меньше шага по итогу не видны
+    #[serde(default = "default_granularity_sol")]
+    pub granularity_sol: u64,
+    // В группе меньше кошельков итог не публикуется, он слишком близок к балансу одного кошелька
+    #[serde(default = "default_min_wallets")]
+    pub min_wallets: usize,
+}
+
+fn default_granularity_sol() -> u64 {
+    1
+}
+
+fn default_min_wallets() -> usize {
+    3
+}
+
+fn default_title() -> String {
+    "Wallet balances".to_string()
+}
+
+fn default_output() -> PathBuf {
+    PathBuf::from("status")
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublicWallet {
+    pub address: String,
+    pub label: Option<String>,
+}
+
+// Содержимое status.json; при скрытии балансов вместо лампортов публикуются диапазоны
+#[derive(Debug, Serialize)]
+struct Status {
+    title: String,
+    generated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_range: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    wallets: Vec<WalletStatus>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    groups: Vec<GroupStatus>,
+    // Итог по кошелькам для страницы; без кошельков не выводится
+    #[serde(skip)]
+    total: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct GroupStatus {
+    name: String,
+    wallets: usize,
+    granularity_sol: u64,
+    // Округленный итог; None - баланс части кошельков не получен
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_range: Option<String>,
+    #[serde(skip)]
+    total: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct WalletStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    balance_range: Option<String>,
+    slot: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    components: Option<Components>,
+    // Баланс для страницы; None - RPC не вернул баланс
+    #[serde(skip)]
+    balance: Option<String>,
+}
+
+// Публикуемые кошельки: явный список и группа, без повторов
+fn public_wallets(
+    config: &StatusPageConfig,
+    wallets: &[String],
+    group_configs: &HashMap<String, GroupConfig>,
+) -> Result<Vec<PublicWallet>> {
+    let mut selected: Vec<PublicWallet> = config
+        .wallets
+        .iter()
+        .map(|wallet| PublicWallet { address: wallet.address.clone(), label: wallet.label.clone() })
+        .collect();
+    if let Some(group) = &config.group {
+        for address in groups::Groups::new(wallets, group_configs).resolve(group)? {
+            if !selected.iter().any(|wallet| wallet.address == address) {
+                selected.push(PublicWallet { address, label: None });
+            }
+        }
+    }
+    Ok(selected)
+}
+
+// Генерация страницы; файлы заменяются целиком, чтобы веб-сервер не отдал недописанный файл
+pub async fn generate(
+    rpc_url: &str,
+    config: &StatusPageConfig,
+    wallets: &[String],
+    group_configs: &HashMap<String, GroupConfig>,
+) -> Result<()> {
+    let mut selected = public_wallets(config, wallets, group_configs)?;
+    if selected.is_empty() && config.aggregates.is_empty() {
+        anyhow::bail!(
+            "status_page has nothing to publish; list wallets in status_page.wallets, status_page.group or status_page.aggregates"
+        );
+    }
+
+    // Кошельки групп с итогами не публикуются отдельно, даже если перечислены в wallets или group
+    let resolver = groups::Groups::new(wallets, group_configs);
+    let mut resolved = Vec::new();
+    for aggregate in &config.aggregates {
+        resolved.push((aggregate, resolver.resolve(&aggregate.group)?));
+    }
+    selected.retain(|wallet| {
+        let aggregate = resolved.iter().find(|(_, members)| members.contains(&wallet.address));
+        if let Some((aggregate, _)) = aggregate {
+            println!("Wallet {} not published individually: member of aggregated group {}", format::address(&wallet.address), aggregate.group);
+        }
+        aggregate.is_none()
+    });
+
+    // Балансы запрашиваются один раз для кошельков и всех групп с итогами
+    let mut aggregated = Vec::new();
+    for (aggregate, members) in resolved {
+        if members.len() < aggregate.min_wallets {
+            println!(
+                "Group {} not published: {} wallets, below min_wallets {}",
+                aggregate.group, members.len(), aggregate.min_wallets
+            );
+            continue;
+        }
+        aggregated.push((aggregate, members));
+    }
+    let mut addresses: Vec<String> = selected.iter().map(|wallet| wallet.address.clone()).collect();
+    for (_, members) in &aggregated {
+        for address in members {
+            if !addresses.contains(address) {
+                addresses.push(address.clone());
+            }
+        }
+    }
+    let balances = get_wallet_balances(&[rpc_url.to_string()], &addresses).await?;
+    let find = |address: &str| -> Option<&WalletBalance> { balances.iter().find(|balance| balance.address == address) };
+    let components = if config.components {
+        let addresses: Vec<String> = selected.iter().map(|wallet| wallet.address.clone()).collect();
+        components::get_components(rpc_url, &addresses).await
+    } else {
+        HashMap::new()
+    };
+
+    // Точные суммы и метки скрываются правилами display.reports.status-page или --redact
+    let exact = !format::redacts(Redact::Balances);
+    let labels = !format::redacts(Redact::Labels);
+    let total: u64 = selected.iter().filter_map(|wallet| find(&wallet.address)).map(|balance| balance.lamports).sum();
+    let published = !selected.is_empty();
+    let status = Status {
+        title: config.title.clone(),
+        generated_at: Utc::now().to_rfc3339(),
+        total_lamports: (published && exact).then_some(total),
+        total_range: (published && !exact).then(|| format::balance(total)),
+        wallets: selected
+            .iter()
+            .enumerate()
+            .map(|(i, wallet)| {
+                let found = find(&wallet.address);
+                WalletStatus {
+                    address: (!config.hide_addresses).then(|| format::address(&wallet.address)),
+                    // Без адреса строку нужно как-то подписать
+                    label: wallet
+                        .label
+                        .clone()
+                        .filter(|_| labels)
+                        .or_else(|| config.hide_addresses.then(|| format!("Wallet {}", i + 1))),
+                    lamports: found.filter(|_| exact).map(|balance| balance.lamports),
+                    balance_range: found.filter(|_| !exact).map(|balance| format::balance(balance.lamports)),
+                    slot: found.and_then(|balance| balance.slot),
+                    // Составляющие выдали бы точный баланс, поэтому при скрытии балансов не публикуются
+                    components: components.get(&wallet.address).copied().filter(|_| exact),
+                    balance: found.map(|balance| format::balance(balance.lamports)),
+                }
+            })
+            .collect(),
+        groups: aggregated
+            .iter()
+            .map(|(aggregate, members)| {
+                let found: Option<Vec<&WalletBalance>> = members.iter().map(|address| find(address)).collect();
+                let rounded = found.map(|found| {
+                    round(found.iter().map(|balance| balance.lamports).sum(), aggregate.granularity_sol)
+                });
+                GroupStatus {
+                    name: aggregate.group.clone(),
+                    wallets: members.len(),
+                    granularity_sol: aggregate.granularity_sol,
+                    total_lamports: rounded.filter(|_| exact),
+                    total_range: rounded.filter(|_| !exact).map(format::balance),
+                    total: rounded.map(format::balance),
+                }
+            })
+            .collect(),
+        total: published.then(|| format::balance(total)),
+    };
+
+    fs::create_dir_all(&config.output).with_context(|| format!("Failed to create {:?}", config.output))?;
+    write_atomic(&config.output.join("status.json"), &serde_json::to_string_pretty(&status)?)?;
+    write_atomic(&config.output.join("index.html"), &render_html(&status))?;
+
+    let failed = status.wallets.iter().filter(|wallet| wallet.balance.is_none()).count()
+        + status.groups.iter().filter(|group| group.total.is_none()).count();
+    println!(
+        "Status page for {} wallets and {} groups written to {:?}{}",
+        status.wallets.len(),
+        status.groups.len(),
+        config.output,
+        if failed > 0 { format!(" ({} balances unavailable)", failed) } else { String::new() }
+    );
+
+    Ok(())
+}
+
+// Округление до ближайшего кратного granularity_sol
+fn round(lamports: u64, granularity_sol: u64) -> u64 {
+    let step = granularity_sol.max(1).saturating_mul(1_000_000_000);
+    lamports.saturating_add(step / 2) / step * step
+}
+
+fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, content).with_context(|| format!("Failed to write {:?}", temp))?;
+    fs::rename(&temp, path).with_context(|| format!("Failed to replace {:?}", path))
+}
+
+fn render_html(status: &Status) -> String {
+    let amount = |balance: &Option<String>| balance.as_ref().map_or_else(|| "unavailable".to_string(), |balance| format!("{} SOL", balance));
+
+    let mut tables = String::new();
+    if !status.wallets.is_empty() {
+        let rows: Vec<(String, String)> = status
+            .wallets
+            .iter()
+            .map(|wallet| {
+                let name = match (&wallet.label, &wallet.address) {
+                    (Some(label), Some(address)) => format!("{}<br><code>{}</code>", escape(label), escape(address)),
+                    (Some(label), None) => escape(label),
+                    (None, Some(address)) => format!("<code>{}</code>", escape(address)),
+                    (None, None) => String::new(),
+                };
+                let name = match &wallet.components {
+                    Some(components) => format!("{}<br><small>{}</small>", name, escape(&components.describe())),
+                    None => name,
+                };
+                (name, amount(&wallet.balance))
+            })
+            .collect();
+        tables.push_str(&format!("  <p>Total: {}</p>\n", escape(&amount(&status.total))));
+        tables.push_str(&table("Wallet", &rows));
+    }
+    if !status.groups.is_empty() {
+        let rows: Vec<(String, String)> = status
+            .groups
+            .iter()
+            .map(|group| {
+                let name = format!("{} ({} wallets, rounded to {} SOL)", escape(&group.name), group.wallets, group.granularity_sol);
+                (name, amount(&group.total))
+            })
+            .collect();
+        tables.push_str(&table("Group", &rows));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>{title}</title>
+  <style>
+    body {{ font-family: sans-serif; max-width: 56em; margin: 2em auto; }}
+    table {{ border-collapse: collapse; width: 100%; margin-bottom: 2em; }}
+    td, th {{ border-bottom: 1px solid #ddd; padding: 0.5em; text-align: left; }}
+    .amount {{ text-align: right; white-space: nowrap; }}
+  </style>
+</head>
+<body>
+  <h1>{title}</h1>
+  <p>Updated {generated_at}. Data: <a href="status.json">status.json</a></p>
+{tables}</body>
+</html>
+"#,
+        title = escape(&status.title),
+        generated_at = escape(&status.generated_at),
+        tables = tables,
+    )
+}
+
+// Таблица сумм; названия строк уже экранированы
+fn table(header: &str, rows: &[(String, String)]) -> String {
+    let mut html = format!("  <table>\n    <thead><tr><th>{}</th><th class=\"amount\">Balance</th></tr></thead>\n    <tbody>\n", header);
+    for (name, amount) in rows {
+        html.push_str(&format!("      <tr><td>{}</td><td class=\"amount\">{}</td></tr>\n", name, escape(amount)));
+    }
+    html.push_str("    </tbody>\n  </table>\n");
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
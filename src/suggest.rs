@@ -0,0 +1,132 @@
+// src/suggest.rs
+// Адреса, часто взаимодействующие с отслеживаемыми кошельками, как кандидаты в мониторинг
+use anyhow::{Context as AnyhowContext, Result};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::flows;
+use crate::format;
+use crate::import;
+use crate::init;
+use crate::lookalike;
+
+// Параметры отчета
+#[derive(Debug)]
+pub struct SuggestOptions<'a> {
+    // Сколько последних транзакций каждого кошелька анализировать
+    pub limit: usize,
+    // Минимальное число переводов с отслеживаемыми кошельками для частого контрагента
+    pub min_transfers: usize,
+    // Спрашивать подтверждение и добавлять выбранные адреса в wallets
+    pub add: bool,
+    // Добавлять и адреса, похожие на известные
+    pub force: bool,
+    pub config_path: &'a Path,
+    // Все адреса конфигурации для проверки на опечатки и двойники
+    pub known: &'a [String],
+}
+
+// Связи неотслеживаемого адреса с отслеживаемыми кошельками
+#[derive(Debug, Default)]
+struct Candidate {
+    transfers: usize,
+    lamports: u64,
+    // Кошельки, которым адрес отправлял SOL и от которых получал
+    sent_to: BTreeSet<String>,
+    received_from: BTreeSet<String>,
+}
+
+impl Candidate {
+    // Признаки связи; пусто, если адрес не стоит предлагать
+    fn reasons(&self, min_transfers: usize) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.transfers >= min_transfers {
+            reasons.push(format!("{} transfers with tracked wallets", self.transfers));
+        }
+        // Общий источник пополнения нескольких отслеживаемых кошельков
+        if self.sent_to.len() >= 2 {
+            reasons.push(format!("funded {} tracked wallets", self.sent_to.len()));
+        }
+        let round_trips = self.sent_to.intersection(&self.received_from).count();
+        if round_trips > 0 {
+            reasons.push(format!("round trips with {} tracked wallets", round_trips));
+        }
+        reasons
+    }
+}
+
+// Отчет с кандидатами; с add каждый адрес добавляется только после явного подтверждения
+pub async fn run(rpc_url: &str, wallets: &[String], options: &SuggestOptions<'_>) -> Result<()> {
+    let tracked: HashSet<&String> = wallets.iter().collect();
+    let transfers = flows::collect(rpc_url, wallets, options.limit).await?;
+
+    let mut candidates: HashMap<&String, Candidate> = HashMap::new();
+    for transfer in &transfers {
+        let (address, wallet, outgoing) = match (tracked.contains(&transfer.from), tracked.contains(&transfer.to)) {
+            (true, false) => (&transfer.to, &transfer.from, false),
+            (false, true) => (&transfer.from, &transfer.to, true),
+            _ => continue,
+        };
+        let candidate = candidates.entry(address).or_default();
+        candidate.transfers += 1;
+        candidate.lamports += transfer.lamports;
+        if outgoing {
+            candidate.sent_to.insert(wallet.clone());
+        } else {
+            candidate.received_from.insert(wallet.clone());
+        }
+    }
+
+    let mut suggested: Vec<(&String, Candidate, Vec<String>)> = candidates
+        .into_iter()
+        .filter_map(|(address, candidate)| {
+            let reasons = candidate.reasons(options.min_transfers);
+            (!reasons.is_empty()).then_some((address, candidate, reasons))
+        })
+        .collect();
+    suggested.sort_by(|a, b| b.1.transfers.cmp(&a.1.transfers).then(b.1.lamports.cmp(&a.1.lamports)).then(a.0.cmp(b.0)));
+
+    println!(
+        "Suggested addresses from {} transfers in the last {} transactions of {} wallets:",
+        transfers.len(), options.limit, wallets.len()
+    );
+    for (address, candidate, reasons) in &suggested {
+        println!("{}: {} SOL total; {}", address, format::sol(candidate.lamports), reasons.join(", "));
+    }
+    if suggested.is_empty() {
+        println!("No candidates");
+    }
+    if !options.add || suggested.is_empty() {
+        return Ok(());
+    }
+
+    // Добавление только подтвержденных адресов; по умолчанию - нет
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut confirmed = Vec::new();
+    for (address, _, reasons) in &suggested {
+        let Some(answer) = init::prompt(&mut input, &format!("Add {} to wallets? [y/N]: ", address))? else {
+            break;
+        };
+        if matches!(answer.to_lowercase().as_str(), "y" | "yes") {
+            confirmed.push((address.to_string(), Some(format!("suggested: {}", reasons.join(", ")))));
+        }
+    }
+    if confirmed.is_empty() {
+        println!("No addresses added");
+        return Ok(());
+    }
+    let addresses: Vec<&str> = confirmed.iter().map(|(address, _)| address.as_str()).collect();
+    lookalike::check_new(&addresses, options.known, options.force)?;
+
+    let config = fs::read_to_string(options.config_path)
+        .with_context(|| format!("Failed to open config file: {:?}", options.config_path))?;
+    let updated = import::append_wallets(&config, &confirmed)?;
+    fs::write(options.config_path, updated)
+        .with_context(|| format!("Failed to write config file: {:?}", options.config_path))?;
+    println!("{} wallets added to {:?}", confirmed.len(), options.config_path);
+
+    Ok(())
+}
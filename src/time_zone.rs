@@ -0,0 +1,418 @@
+// src/time_zone.rs
+// Часовой пояс вывода времени: файл TZif из системной базы tzdata или строка POSIX TZ.
+// Смещение считается внутри процесса, переменная окружения TZ не используется
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::{Datelike, NaiveDate};
+use std::fs;
+use std::path::PathBuf;
+
+// Каталог системной базы tzdata, если не задан TZDIR
+const DEFAULT_ZONEINFO: &str = "/usr/share/zoneinfo";
+
+// Начало и конец летнего времени по умолчанию в POSIX TZ без правил (как в glibc)
+const DEFAULT_RULES: &str = "M3.2.0,M11.1.0";
+
+// Время перехода по умолчанию - 02:00 местного времени
+const DEFAULT_TRANSITION_TIME: i64 = 2 * 3600;
+
+#[derive(Debug, Clone)]
+pub struct TimeZone {
+    name: String,
+    // Моменты переходов (unix time) и номер смещения, действующего после каждого
+    transitions: Vec<(i64, usize)>,
+    // Смещения от UTC в секундах; первое действует до первого перехода
+    offsets: Vec<i32>,
+    // Правило для времени после последнего перехода (из строки в конце файла TZif)
+    rule: Option<PosixRule>,
+}
+
+impl TimeZone {
+    // Пояс по имени IANA (Europe/Berlin) из TZDIR или /usr/share/zoneinfo,
+    // иначе по строке POSIX TZ (CET-1CEST,M3.5.0,M10.5.0/3) - она не требует базы tzdata
+    pub fn load(name: &str) -> Result<Self> {
+        let zoneinfo = std::env::var_os("TZDIR").map_or_else(|| PathBuf::from(DEFAULT_ZONEINFO), PathBuf::from);
+        let valid_path = !name.is_empty() && !name.split('/').any(|part| part.is_empty() || part == "." || part == "..");
+        let path = zoneinfo.join(name);
+        if valid_path && path.is_file() {
+            let data = fs::read(&path).with_context(|| format!("Failed to read time zone file {:?}", path))?;
+            let mut zone = parse_tzif(&data).with_context(|| format!("Invalid time zone file {:?}", path))?;
+            zone.name = name.to_string();
+            return Ok(zone);
+        }
+
+        let spec = if name.eq_ignore_ascii_case("UTC") { "UTC0" } else { name };
+        match PosixRule::parse(spec) {
+            Ok(rule) => Ok(TimeZone { name: name.to_string(), transitions: Vec::new(), offsets: vec![rule.std_offset], rule: Some(rule) }),
+            Err(_) => anyhow::bail!(
+                "Unknown time zone: {} (expected an IANA name like Europe/Berlin, looked in {:?}, or a POSIX TZ string like CET-1CEST,M3.5.0,M10.5.0/3)",
+                name,
+                zoneinfo
+            ),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Смещение от UTC в секундах в момент timestamp
+    pub fn offset_at(&self, timestamp: i64) -> i32 {
+        let last = self.transitions.last().map(|(time, _)| *time);
+        if let Some(rule) = &self.rule {
+            if last.is_none_or(|last| timestamp >= last) {
+                return rule.offset_at(timestamp);
+            }
+        }
+        let passed = self.transitions.partition_point(|(time, _)| *time <= timestamp);
+        match passed {
+            0 => self.offsets.first().copied().unwrap_or(0),
+            _ => self.offsets[self.transitions[passed - 1].1],
+        }
+    }
+}
+
+// Разбор TZif (RFC 8536): для версий 2+ берутся 64-битные данные и строка POSIX TZ в конце
+fn parse_tzif(data: &[u8]) -> Result<TimeZone> {
+    let header = Header::parse(data)?;
+    if header.version == 0 {
+        let (zone, _) = parse_block(data, &header, 4)?;
+        return Ok(zone);
+    }
+
+    let second = data.get(header.block_len(4)..).context("TZif file is truncated")?;
+    let header = Header::parse(second)?;
+    let (mut zone, end) = parse_block(second, &header, 8)?;
+    let footer = second.get(end..).unwrap_or_default();
+    let footer = std::str::from_utf8(footer).context("TZif footer is not UTF-8")?;
+    let spec = footer.trim_matches('\n');
+    if !spec.is_empty() {
+        zone.rule = Some(PosixRule::parse(spec).with_context(|| format!("Invalid TZ string in TZif footer: {}", spec))?);
+    }
+    Ok(zone)
+}
+
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+const HEADER_LEN: usize = 44;
+
+impl Header {
+    fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < HEADER_LEN || &data[..4] != b"TZif" {
+            anyhow::bail!("not a TZif file");
+        }
+        let count = |i: usize| u32::from_be_bytes(data[20 + i * 4..24 + i * 4].try_into().unwrap()) as usize;
+        let version = match data[4] {
+            0 => 0,
+            b'2'..=b'9' => data[4] - b'0',
+            other => anyhow::bail!("unsupported TZif version {}", other),
+        };
+        let header = Header {
+            version,
+            isutcnt: count(0),
+            isstdcnt: count(1),
+            leapcnt: count(2),
+            timecnt: count(3),
+            typecnt: count(4),
+            charcnt: count(5),
+        };
+        if header.typecnt == 0 {
+            anyhow::bail!("TZif file has no local time types");
+        }
+        Ok(header)
+    }
+
+    // Длина заголовка с блоком данных при размере времени time_size байт
+    fn block_len(&self, time_size: usize) -> usize {
+        HEADER_LEN
+            + self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+// Переходы и смещения из блока данных; возвращает и конец блока
+fn parse_block(data: &[u8], header: &Header, time_size: usize) -> Result<(TimeZone, usize)> {
+    let end = header.block_len(time_size);
+    if data.len() < end {
+        anyhow::bail!("TZif file is truncated");
+    }
+
+    let times = &data[HEADER_LEN..HEADER_LEN + header.timecnt * time_size];
+    let indices = &data[HEADER_LEN + times.len()..HEADER_LEN + times.len() + header.timecnt];
+    let types = &data[HEADER_LEN + times.len() + indices.len()..][..header.typecnt * 6];
+
+    let offsets: Vec<i32> = types.chunks(6).map(|info| i32::from_be_bytes(info[..4].try_into().unwrap())).collect();
+    let mut transitions = Vec::with_capacity(header.timecnt);
+    for (time, &index) in times.chunks(time_size).zip(indices) {
+        let time = match time_size {
+            4 => i32::from_be_bytes(time.try_into().unwrap()) as i64,
+            _ => i64::from_be_bytes(time.try_into().unwrap()),
+        };
+        if index as usize >= offsets.len() {
+            anyhow::bail!("TZif transition refers to missing local time type {}", index);
+        }
+        transitions.push((time, index as usize));
+    }
+
+    Ok((TimeZone { name: String::new(), transitions, offsets, rule: None }, end))
+}
+
+// Строка POSIX TZ: стандартное смещение и, если есть летнее время, его смещение и даты перехода
+#[derive(Debug, Clone)]
+struct PosixRule {
+    std_offset: i32,
+    dst: Option<Dst>,
+}
+
+#[derive(Debug, Clone)]
+struct Dst {
+    offset: i32,
+    start: Transition,
+    end: Transition,
+}
+
+// День перехода и время в секундах от полуночи местного времени (может быть отрицательным или больше суток)
+#[derive(Debug, Clone, Copy)]
+struct Transition {
+    day: Day,
+    time: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Day {
+    // Jn: день года 1..365 без учета 29 февраля
+    Julian(u32),
+    // n: день года 0..365 с учетом 29 февраля
+    Ordinal(u32),
+    // Mm.w.d: день недели d (0 - воскресенье) w-й недели месяца m, 5 - последняя
+    Weekday { month: u32, week: u32, weekday: u32 },
+}
+
+impl PosixRule {
+    fn parse(spec: &str) -> Result<Self> {
+        let mut parser = Parser { rest: spec };
+        parser.name()?;
+        // В POSIX TZ смещение положительно к западу от Гринвича, здесь - к востоку, как в TZif
+        let std_offset = -parser.seconds()? as i32;
+        if parser.rest.is_empty() {
+            return Ok(PosixRule { std_offset, dst: None });
+        }
+
+        parser.name()?;
+        let offset = match parser.rest.is_empty() || parser.rest.starts_with(',') {
+            true => std_offset + 3600,
+            false => -parser.seconds()? as i32,
+        };
+        let rules = match parser.rest.strip_prefix(',') {
+            Some(rules) => rules,
+            None if parser.rest.is_empty() => DEFAULT_RULES,
+            None => anyhow::bail!("unexpected {:?} in TZ string {}", parser.rest, spec),
+        };
+        let (start, end) = rules.split_once(',').with_context(|| format!("TZ string {} needs start and end rules", spec))?;
+        Ok(PosixRule { std_offset, dst: Some(Dst { offset, start: Transition::parse(start)?, end: Transition::parse(end)? }) })
+    }
+
+    fn offset_at(&self, timestamp: i64) -> i32 {
+        let Some(dst) = &self.dst else {
+            return self.std_offset;
+        };
+        let Some(year) = chrono::DateTime::from_timestamp(timestamp + self.std_offset as i64, 0).map(|time| time.year()) else {
+            return self.std_offset;
+        };
+        // Начало летнего времени задано по стандартному времени, конец - по летнему
+        let (Some(start), Some(end)) = (dst.start.local(year), dst.end.local(year)) else {
+            return self.std_offset;
+        };
+        let start = start - self.std_offset as i64;
+        let end = end - dst.offset as i64;
+        let summer = match start < end {
+            true => start <= timestamp && timestamp < end,
+            // Южное полушарие: летнее время переходит через начало года
+            false => !(end <= timestamp && timestamp < start),
+        };
+        if summer {
+            dst.offset
+        } else {
+            self.std_offset
+        }
+    }
+}
+
+impl Transition {
+    fn parse(text: &str) -> Result<Self> {
+        let (day, time) = match text.split_once('/') {
+            Some((day, time)) => {
+                let mut parser = Parser { rest: time };
+                let seconds = parser.seconds()?;
+                if !parser.rest.is_empty() {
+                    anyhow::bail!("invalid transition time {}", time);
+                }
+                (day, seconds)
+            }
+            None => (text, DEFAULT_TRANSITION_TIME),
+        };
+
+        let number = |value: &str| value.parse::<u32>().with_context(|| format!("invalid transition day {}", text));
+        let day = if let Some(julian) = day.strip_prefix('J') {
+            Day::Julian(number(julian)?)
+        } else if let Some(spec) = day.strip_prefix('M') {
+            let parts: Vec<u32> = spec.split('.').map(number).collect::<Result<_>>()?;
+            match parts[..] {
+                [month @ 1..=12, week @ 1..=5, weekday @ 0..=6] => Day::Weekday { month, week, weekday },
+                _ => anyhow::bail!("invalid transition day {}", text),
+            }
+        } else {
+            Day::Ordinal(number(day)?)
+        };
+        match day {
+            Day::Julian(1..=365) | Day::Ordinal(0..=365) | Day::Weekday { .. } => Ok(Transition { day, time }),
+            _ => anyhow::bail!("invalid transition day {}", text),
+        }
+    }
+
+    // Момент перехода в году year по местным часам, секунды от 1970-01-01 00:00 без учета смещения
+    fn local(&self, year: i32) -> Option<i64> {
+        let january = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        let date = match self.day {
+            Day::Julian(day) => {
+                let leap = january.leap_year() && day >= 60;
+                january + chrono::Days::new((day - 1 + leap as u32) as u64)
+            }
+            Day::Ordinal(day) => january + chrono::Days::new(day as u64),
+            Day::Weekday { month, week, weekday } => {
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let first_weekday = first.weekday().num_days_from_sunday();
+                let mut day = 1 + (weekday + 7 - first_weekday) % 7 + (week - 1) * 7;
+                let next_month = match month {
+                    12 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+                    _ => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+                };
+                let days_in_month = (next_month - first).num_days() as u32;
+                while day > days_in_month {
+                    day -= 7;
+                }
+                first.with_day(day)?
+            }
+        };
+        Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp() + self.time)
+    }
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl Parser<'_> {
+    // Обозначение пояса: не меньше трех букв или <...> с цифрами и знаками
+    fn name(&mut self) -> Result<()> {
+        let len = match self.rest.strip_prefix('<') {
+            Some(quoted) => quoted.find('>').context("unterminated <...> zone name in TZ string")? + 2,
+            None => self.rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(self.rest.len()),
+        };
+        if len < 3 {
+            anyhow::bail!("zone name in TZ string must have at least 3 letters: {}", self.rest);
+        }
+        self.rest = &self.rest[len..];
+        Ok(())
+    }
+
+    // [+-]hh[:mm[:ss]] в секундах
+    fn seconds(&mut self) -> Result<i64> {
+        let (sign, rest) = match self.rest.as_bytes().first() {
+            Some(b'-') => (-1, &self.rest[1..]),
+            Some(b'+') => (1, &self.rest[1..]),
+            _ => (1, self.rest),
+        };
+        let len = rest.find(|c: char| !c.is_ascii_digit() && c != ':').unwrap_or(rest.len());
+        let parts: Vec<&str> = rest[..len].split(':').collect();
+        if parts.len() > 3 || parts.iter().any(|part| part.is_empty() || part.len() > 3) {
+            anyhow::bail!("invalid offset or time in TZ string: {}", self.rest);
+        }
+        let seconds = parts.iter().zip([3600, 60, 1]).map(|(part, unit)| part.parse::<i64>().unwrap_or(0) * unit).sum::<i64>();
+        if seconds > 167 * 3600 {
+            anyhow::bail!("offset or time out of range in TZ string: {}", self.rest);
+        }
+        self.rest = &rest[len..];
+        Ok(sign * seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> i64 {
+        chrono::DateTime::parse_from_rfc3339(text).unwrap().timestamp()
+    }
+
+    #[test]
+    fn posix_rule_switches_on_last_sunday() {
+        let berlin = PosixRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        assert_eq!(berlin.offset_at(at("2024-01-15T12:00:00Z")), 3600);
+        assert_eq!(berlin.offset_at(at("2024-03-31T00:59:59Z")), 3600);
+        assert_eq!(berlin.offset_at(at("2024-03-31T01:00:00Z")), 7200);
+        assert_eq!(berlin.offset_at(at("2024-10-27T00:59:59Z")), 7200);
+        assert_eq!(berlin.offset_at(at("2024-10-27T01:00:00Z")), 3600);
+    }
+
+    #[test]
+    fn posix_rule_in_southern_hemisphere() {
+        let sydney = PosixRule::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert_eq!(sydney.offset_at(at("2024-01-15T00:00:00Z")), 11 * 3600);
+        assert_eq!(sydney.offset_at(at("2024-07-15T00:00:00Z")), 10 * 3600);
+        assert_eq!(sydney.offset_at(at("2024-12-31T23:00:00Z")), 11 * 3600);
+    }
+
+    #[test]
+    fn posix_rule_without_dst_and_quoted_names() {
+        assert_eq!(PosixRule::parse("<+0530>-5:30").unwrap().offset_at(0), 5 * 3600 + 1800);
+        assert_eq!(PosixRule::parse("EST5").unwrap().offset_at(0), -5 * 3600);
+        assert!(PosixRule::parse("Europe/Berlin").is_err());
+        assert!(PosixRule::parse("CET-1CEST,M13.1.0,M10.5.0").is_err());
+    }
+
+    // Минимальный TZif версии 2: один переход в 64-битном блоке и правило в конце
+    fn tzif(transition: i64, footer: &str) -> Vec<u8> {
+        fn header(timecnt: u32, typecnt: u32, charcnt: u32) -> Vec<u8> {
+            let mut header = b"TZif2".to_vec();
+            header.extend([0; 15]);
+            for count in [0, 0, 0, timecnt, typecnt, charcnt] {
+                header.extend(u32::to_be_bytes(count));
+            }
+            header
+        }
+        let mut data = header(0, 1, 4);
+        data.extend([0, 0, 0, 0, 0, 0]);
+        data.extend(b"LMT\0");
+        data.extend(header(1, 2, 8));
+        data.extend(transition.to_be_bytes());
+        data.push(1);
+        data.extend(i32::to_be_bytes(1800));
+        data.extend([0, 0]);
+        data.extend(i32::to_be_bytes(3600));
+        data.extend([0, 4]);
+        data.extend(b"LMT\0CET\0");
+        data.extend(format!("\n{}\n", footer).as_bytes());
+        data
+    }
+
+    #[test]
+    fn tzif_uses_transitions_then_footer() {
+        let zone = parse_tzif(&tzif(1_000, "CET-1CEST,M3.5.0,M10.5.0/3")).unwrap();
+        assert_eq!(zone.offset_at(999), 1800);
+        assert_eq!(zone.offset_at(1_000), 3600);
+        assert_eq!(zone.offset_at(at("2024-07-01T00:00:00Z")), 7200);
+        assert!(parse_tzif(b"TZif2").is_err());
+    }
+}
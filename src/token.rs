@@ -0,0 +1,114 @@
+// src/token.rs
+// Токен-аккаунты SPL Token и Token-2022, принадлежащие кошельку
+use anyhow::{Context as AnyhowContext, Result};
+use serde_json::{json, Value};
+
+use crate::account::{self, KeyedAccount};
+use crate::rpc;
+use crate::transaction::{self, AccountMeta, Instruction};
+
+// Номера инструкций SPL Token (совпадают в Token-2022)
+const REVOKE_INSTRUCTION: u8 = 5;
+const CLOSE_ACCOUNT_INSTRUCTION: u8 = 9;
+
+// Токен-аккаунт с интересующими нас полями
+#[derive(Debug)]
+pub struct TokenAccount {
+    pub address: String,
+    // Программа, которой принадлежит аккаунт (Token или Token-2022)
+    pub program: &'static str,
+    pub mint: String,
+    // Баланс в минимальных единицах токена
+    pub amount: u64,
+    pub decimals: u32,
+    pub delegate: Option<String>,
+    pub delegated_amount: u64,
+    pub close_authority: Option<String>,
+    pub state: String,
+    // Лампорты аккаунта (рента), возвращаемые при закрытии
+    pub lamports: u64,
+}
+
+// Токен-аккаунты кошелька в обеих токен-программах
+pub async fn get_token_accounts(rpc_url: &str, owner: &str) -> Result<Vec<TokenAccount>> {
+    let mut accounts = Vec::new();
+
+    for program in [account::TOKEN_PROGRAM, account::TOKEN_2022_PROGRAM] {
+        let response: rpc::WithContext<Vec<KeyedAccount>> = rpc::call_with_context(
+            rpc_url,
+            "getTokenAccountsByOwner",
+            json!([owner, { "programId": program }, { "encoding": "jsonParsed" }]),
+        )
+        .await
+        .with_context(|| format!("Failed to fetch token accounts of {}", owner))?;
+
+        for keyed in response.value {
+            let info = account::parsed_info(&keyed.account)
+                .with_context(|| format!("Token account {} has no parsed data", keyed.pubkey))?;
+            accounts.push(parse_token_account(keyed.pubkey, program, keyed.account.lamports, info));
+        }
+    }
+
+    Ok(accounts)
+}
+
+// Число знаков токена из аккаунта mint
+pub async fn mint_decimals(rpc_url: &str, mint: &str) -> Result<u32> {
+    let info = account::get_account_info(rpc_url, mint).await?
+        .with_context(|| format!("Mint {} not found", mint))?;
+
+    account::parsed_info(&info)
+        .and_then(|info| info.get("decimals"))
+        .and_then(Value::as_u64)
+        .map(|decimals| decimals as u32)
+        .with_context(|| format!("Mint {} has no decimals", mint))
+}
+
+// Разбор jsonParsed данных токен-аккаунта
+pub fn parse_token_account(address: String, program: &'static str, lamports: u64, info: &Value) -> TokenAccount {
+    let text = |pointer: &str| info.pointer(pointer).and_then(Value::as_str).map(str::to_string);
+    let units = |pointer: &str| {
+        info.pointer(pointer)
+            .and_then(Value::as_str)
+            .and_then(|amount| amount.parse().ok())
+            .unwrap_or(0)
+    };
+
+    TokenAccount {
+        address,
+        program,
+        mint: text("/mint").unwrap_or_default(),
+        amount: units("/tokenAmount/amount"),
+        decimals: info.pointer("/tokenAmount/decimals").and_then(Value::as_u64).unwrap_or(0) as u32,
+        delegate: text("/delegate"),
+        delegated_amount: units("/delegatedAmount/amount"),
+        close_authority: text("/closeAuthority"),
+        state: text("/state").unwrap_or_default(),
+        lamports,
+    }
+}
+
+// Инструкция Revoke: снятие делегата с токен-аккаунта
+pub fn revoke_instruction(account: &TokenAccount, owner: &transaction::Pubkey) -> Result<Instruction> {
+    Ok(Instruction {
+        program_id: transaction::decode_pubkey(account.program)?,
+        accounts: vec![
+            AccountMeta { pubkey: transaction::decode_pubkey(&account.address)?, is_signer: false, is_writable: true },
+            AccountMeta { pubkey: *owner, is_signer: true, is_writable: false },
+        ],
+        data: vec![REVOKE_INSTRUCTION],
+    })
+}
+
+// Инструкция CloseAccount: закрытие пустого аккаунта с возвратом ренты владельцу
+pub fn close_account_instruction(account: &TokenAccount, owner: &transaction::Pubkey) -> Result<Instruction> {
+    Ok(Instruction {
+        program_id: transaction::decode_pubkey(account.program)?,
+        accounts: vec![
+            AccountMeta { pubkey: transaction::decode_pubkey(&account.address)?, is_signer: false, is_writable: true },
+            AccountMeta { pubkey: *owner, is_signer: false, is_writable: true },
+            AccountMeta { pubkey: *owner, is_signer: true, is_writable: false },
+        ],
+        data: vec![CLOSE_ACCOUNT_INSTRUCTION],
+    })
+}
@@ -0,0 +1,158 @@
+// src/token_anomalies.rs
+// Смена владельца, полномочия закрытия и закрытие токен-аккаунтов отслеживаемых кошельков по истории транзакций
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::account;
+use crate::flows;
+use crate::format;
+use crate::token;
+use crate::transaction;
+use crate::tx;
+
+// Номер инструкции SetAuthority в токен-программах и тип полномочия закрытия аккаунта
+const SET_AUTHORITY: u8 = 6;
+const CLOSE_ACCOUNT_AUTHORITY: u8 = 3;
+
+// Токен-аккаунт кошелька до транзакции: индекс в ключах транзакции и mint
+struct Owned {
+    index: u64,
+    mint: String,
+}
+
+// Аномалии одной транзакции для кошелька
+fn anomalies(transaction: &Value, wallet: &str) -> Result<Vec<String>> {
+    let keys = flows::account_keys(transaction)?;
+    let token_balances = |field: &str| -> Vec<Value> {
+        transaction
+            .pointer(&format!("/meta/{}", field))
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let pre = token_balances("preTokenBalances");
+    let post = token_balances("postTokenBalances");
+
+    let owned: Vec<Owned> = pre
+        .iter()
+        .filter(|balance| balance.get("owner").and_then(Value::as_str) == Some(wallet))
+        .filter_map(|balance| {
+            Some(Owned {
+                index: balance.get("accountIndex")?.as_u64()?,
+                mint: balance.get("mint")?.as_str()?.to_string(),
+            })
+        })
+        .collect();
+    if owned.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut found = Vec::new();
+    let address = |index: u64| keys.get(index as usize).cloned().unwrap_or_else(|| format!("#{}", index));
+    for account in &owned {
+        let after = post.iter().find(|balance| balance.get("accountIndex").and_then(Value::as_u64) == Some(account.index));
+        match after.and_then(|balance| balance.get("owner")).and_then(Value::as_str) {
+            // Закрытый аккаунт не попадает в postTokenBalances
+            None if after.is_none() => {
+                found.push(format!("token account {} (mint {}) was closed", address(account.index), account.mint))
+            }
+            Some(owner) if owner != wallet => found.push(format!(
+                "token account {} (mint {}) owner changed to {}",
+                address(account.index), account.mint, owner
+            )),
+            _ => {}
+        }
+    }
+
+    // Новое полномочие закрытия видно только в данных SetAuthority
+    let outer = transaction.pointer("/transaction/message/instructions").and_then(Value::as_array);
+    let inner = transaction
+        .pointer("/meta/innerInstructions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|group| group.get("instructions")?.as_array());
+    for instruction in outer.into_iter().chain(inner).flatten() {
+        let program = instruction.get("programIdIndex").and_then(Value::as_u64).map(address);
+        if !matches!(program.as_deref(), Some(account::TOKEN_PROGRAM | account::TOKEN_2022_PROGRAM)) {
+            continue;
+        }
+        let Some(target) = instruction.pointer("/accounts/0").and_then(Value::as_u64) else {
+            continue;
+        };
+        let Some(account) = owned.iter().find(|account| account.index == target) else {
+            continue;
+        };
+        let data = instruction
+            .get("data")
+            .and_then(Value::as_str)
+            .and_then(|data| bs58::decode(data).into_vec().ok())
+            .unwrap_or_default();
+        if data.len() >= 3 && data[0] == SET_AUTHORITY && data[1] == CLOSE_ACCOUNT_AUTHORITY {
+            let authority = match data.get(3..35) {
+                Some(key) if data[2] == 1 => transaction::encode_pubkey(key.try_into()?),
+                _ => "none".to_string(),
+            };
+            found.push(format!(
+                "token account {} (mint {}) close authority set to {}",
+                address(target), account.mint, authority
+            ));
+        }
+    }
+
+    Ok(found)
+}
+
+// Проверка последних limit транзакций каждого кошелька и его текущих токен-аккаунтов
+// (их закрывают и без подписи кошелька); только не старше since (unix time), если задано
+pub async fn run_report(rpc_url: &str, wallets: &[String], limit: usize, since: Option<i64>) -> Result<()> {
+    let mut alerts = 0;
+
+    for wallet in wallets {
+        let mut addresses = vec![wallet.clone()];
+        addresses.extend(token::get_token_accounts(rpc_url, wallet).await?.into_iter().map(|account| account.address));
+
+        let mut seen = HashSet::new();
+        for address in &addresses {
+            let signatures = tx::get_signatures(rpc_url, address, limit).await?;
+            let fresh: Vec<&tx::SignatureInfo> = signatures
+                .iter()
+                .filter(|info| info.err.is_none() && seen.insert(info.signature.clone()))
+                .filter(|info| match (since, info.block_time) {
+                    (Some(since), Some(time)) => time >= since,
+                    _ => true,
+                })
+                .collect();
+
+            for (info, transaction) in tx::get_transactions(rpc_url, fresh).await {
+                let parsed = transaction.and_then(|transaction| {
+                    let payer = flows::account_keys(&transaction)?.into_iter().next().unwrap_or_default();
+                    Ok((payer, anomalies(&transaction, wallet)?))
+                });
+                let (payer, found) = match parsed {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        println!("Skipping transaction {}: {:#}", info.signature, e);
+                        continue;
+                    }
+                };
+                let time = info.block_time.map_or_else(|| "unknown time".to_string(), format::time);
+                for anomaly in found {
+                    alerts += 1;
+                    println!(
+                        "[ALERT] {}: {} in {} at {} (fee payer {})",
+                        wallet, anomaly, info.signature, time, payer
+                    );
+                }
+            }
+        }
+    }
+
+    if alerts > 0 {
+        anyhow::bail!("{} token account ownership anomalies", alerts);
+    }
+    println!("No token account ownership anomalies for {} tracked wallets", wallets.len());
+
+    Ok(())
+}
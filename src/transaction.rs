@@ -0,0 +1,333 @@
+// src/transaction.rs
+// Минимальная сборка, подпись и отправка legacy-транзакций Solana
+use anyhow::{Context as AnyhowContext, Result};
+use base64::Engine;
+use ed25519_dalek::{Signer, SigningKey};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use crate::address_audit;
+use crate::rpc;
+
+pub type Pubkey = [u8; 32];
+
+// Максимальный размер сериализованной транзакции
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+// Аккаунт, участвующий в инструкции
+#[derive(Debug, Clone)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+// Инструкция программы
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+// Транзакция с одной подписью плательщика комиссии
+#[derive(Debug)]
+pub struct Transaction {
+    // Пустая (нулевая) подпись означает, что транзакция еще не подписана
+    pub signature: [u8; 64],
+    pub message: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LatestBlockhash {
+    blockhash: String,
+}
+
+impl Transaction {
+    // Неподписанная транзакция для офлайн-подписи
+    pub fn new_unsigned(payer: &Pubkey, instructions: &[Instruction], blockhash: &Pubkey) -> Self {
+        Transaction {
+            signature: [0; 64],
+            message: compile_message(payer, instructions, blockhash),
+        }
+    }
+
+    pub fn sign(&mut self, keypair: &SigningKey) {
+        self.signature = keypair.sign(&self.message).to_bytes();
+    }
+
+    // Сериализация: shortvec числа подписей, подписи, сообщение
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + 64 + self.message.len());
+        encode_length(&mut bytes, 1);
+        bytes.extend_from_slice(&self.signature);
+        bytes.extend_from_slice(&self.message);
+        bytes
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.serialize())
+    }
+
+    // Подпись транзакции служит ее идентификатором
+    pub fn signature_base58(&self) -> String {
+        bs58::encode(self.signature).into_string()
+    }
+}
+
+// Разбиение инструкций на транзакции, укладывающиеся в ограничение размера пакета
+pub fn pack(payer: &Pubkey, instructions: Vec<Instruction>, blockhash: &Pubkey) -> Result<Vec<Transaction>> {
+    let mut transactions = Vec::new();
+    let mut batch: Vec<Instruction> = Vec::new();
+
+    for instruction in instructions {
+        let mut candidate = batch.clone();
+        candidate.push(instruction.clone());
+        if Transaction::new_unsigned(payer, &candidate, blockhash).serialize().len() <= PACKET_DATA_SIZE {
+            batch = candidate;
+            continue;
+        }
+
+        if batch.is_empty() {
+            anyhow::bail!("A single instruction does not fit into a transaction");
+        }
+        transactions.push(Transaction::new_unsigned(payer, &batch, blockhash));
+        batch = vec![instruction];
+    }
+    if !batch.is_empty() {
+        transactions.push(Transaction::new_unsigned(payer, &batch, blockhash));
+    }
+
+    Ok(transactions)
+}
+
+// Разбор адреса в кодировке base58
+pub fn decode_pubkey(address: &str) -> Result<Pubkey> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .with_context(|| format!("Invalid base58 address: {}", address))?;
+
+    bytes.try_into()
+        .map_err(|bytes: Vec<u8>| anyhow::anyhow!("Address {} is {} bytes, expected 32", address, bytes.len()))
+}
+
+pub fn encode_pubkey(pubkey: &Pubkey) -> String {
+    bs58::encode(pubkey).into_string()
+}
+
+// Адрес PDA программы по seeds, как find_program_address в solana-program: первый bump от 255 вниз, дающий точку вне кривой
+pub fn find_program_address(seeds: &[&[u8]], program: &Pubkey) -> Result<(Pubkey, u8)> {
+    for bump in (0..=u8::MAX).rev() {
+        let mut hasher = Sha256::new();
+        for seed in seeds {
+            hasher.update(seed);
+        }
+        hasher.update([bump]);
+        hasher.update(program);
+        hasher.update(b"ProgramDerivedAddress");
+        let address: Pubkey = hasher.finalize().into();
+        if !address_audit::is_on_curve(&address) {
+            return Ok((address, bump));
+        }
+    }
+    anyhow::bail!("No program address found for program {}", encode_pubkey(program))
+}
+
+// Ключ из файла в формате Solana CLI (JSON массив из 64 байт)
+pub fn load_keypair(path: &Path) -> Result<SigningKey> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read keypair file: {:?}", path))?;
+    let bytes: Vec<u8> = serde_json::from_str(&content)
+        .with_context(|| format!("Keypair file {:?} is not a JSON byte array", path))?;
+    let bytes: [u8; 64] = bytes.try_into()
+        .map_err(|_| anyhow::anyhow!("Keypair file {:?} must contain 64 bytes", path))?;
+
+    SigningKey::from_keypair_bytes(&bytes)
+        .with_context(|| format!("Keypair file {:?} has a mismatched public key", path))
+}
+
+// Последний blockhash для новой транзакции
+pub async fn latest_blockhash(rpc_url: &str) -> Result<Pubkey> {
+    let latest: rpc::WithContext<LatestBlockhash> = rpc::call_with_context(
+        rpc_url,
+        "getLatestBlockhash",
+        json!([{ "commitment": "finalized" }]),
+    )
+    .await
+    .context("Failed to fetch latest blockhash")?;
+
+    decode_pubkey(&latest.value.blockhash)
+}
+
+// Отправка подписанной транзакции; возвращает подпись
+pub async fn send(rpc_url: &str, transaction: &Transaction) -> Result<String> {
+    rpc::call(
+        rpc_url,
+        "sendTransaction",
+        json!([transaction.to_base64(), { "encoding": "base64" }]),
+    )
+    .await
+    .context("Failed to send transaction")
+}
+
+// Компиляция legacy-сообщения: заголовок, ключи, blockhash, инструкции
+fn compile_message(payer: &Pubkey, instructions: &[Instruction], blockhash: &Pubkey) -> Vec<u8> {
+    // Плательщик всегда первый, подписывающий и изменяемый
+    let mut metas: Vec<AccountMeta> = vec![AccountMeta { pubkey: *payer, is_signer: true, is_writable: true }];
+    let mut add = |meta: AccountMeta| match metas.iter_mut().find(|m| m.pubkey == meta.pubkey) {
+        Some(existing) => {
+            existing.is_signer |= meta.is_signer;
+            existing.is_writable |= meta.is_writable;
+        }
+        None => metas.push(meta),
+    };
+    for instruction in instructions {
+        for meta in &instruction.accounts {
+            add(meta.clone());
+        }
+        add(AccountMeta { pubkey: instruction.program_id, is_signer: false, is_writable: false });
+    }
+
+    // Порядок ключей: подписывающие изменяемые, подписывающие, изменяемые, остальные
+    metas.sort_by_key(|m| match (m.is_signer, m.is_writable) {
+        (true, true) => 0,
+        (true, false) => 1,
+        (false, true) => 2,
+        (false, false) => 3,
+    });
+    let keys: Vec<Pubkey> = metas.iter().map(|m| m.pubkey).collect();
+    let index_of = |pubkey: &Pubkey| keys.iter().position(|k| k == pubkey).unwrap_or_default() as u8;
+
+    let mut message = vec![
+        metas.iter().filter(|m| m.is_signer).count() as u8,
+        metas.iter().filter(|m| m.is_signer && !m.is_writable).count() as u8,
+        metas.iter().filter(|m| !m.is_signer && !m.is_writable).count() as u8,
+    ];
+
+    encode_length(&mut message, keys.len());
+    for key in &keys {
+        message.extend_from_slice(key);
+    }
+    message.extend_from_slice(blockhash);
+
+    encode_length(&mut message, instructions.len());
+    for instruction in instructions {
+        message.push(index_of(&instruction.program_id));
+        encode_length(&mut message, instruction.accounts.len());
+        for meta in &instruction.accounts {
+            message.push(index_of(&meta.pubkey));
+        }
+        encode_length(&mut message, instruction.data.len());
+        message.extend_from_slice(&instruction.data);
+    }
+
+    message
+}
+
+// Длина в формате shortvec (compact-u16)
+fn encode_length(bytes: &mut Vec<u8>, mut len: usize) {
+    loop {
+        let mut byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            bytes.push(byte);
+            return;
+        }
+        byte |= 0x80;
+        bytes.push(byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> Pubkey {
+        [byte; 32]
+    }
+
+    fn length(len: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        encode_length(&mut bytes, len);
+        bytes
+    }
+
+    #[test]
+    fn compact_u16_boundaries() {
+        assert_eq!(length(0), [0x00]);
+        assert_eq!(length(127), [0x7f]);
+        assert_eq!(length(128), [0x80, 0x01]);
+        assert_eq!(length(16383), [0xff, 0x7f]);
+        assert_eq!(length(16384), [0x80, 0x80, 0x01]);
+    }
+
+    #[test]
+    fn message_header_and_key_order() {
+        let (payer, signer, writable, readonly, program, blockhash) = (key(1), key(2), key(3), key(4), key(5), key(9));
+        let instruction = Instruction {
+            program_id: program,
+            accounts: vec![
+                AccountMeta { pubkey: readonly, is_signer: false, is_writable: false },
+                AccountMeta { pubkey: writable, is_signer: false, is_writable: true },
+                AccountMeta { pubkey: signer, is_signer: true, is_writable: false },
+            ],
+            data: vec![7, 8],
+        };
+        let message = compile_message(&payer, &[instruction], &blockhash);
+
+        // 2 подписи, из них 1 только для чтения; 2 неподписывающих только для чтения (readonly и программа)
+        assert_eq!(message[..3], [2, 1, 2]);
+        assert_eq!(message[3], 5);
+        let keys: Vec<&[u8]> = message[4..4 + 5 * 32].chunks(32).collect();
+        assert_eq!(keys, [&payer[..], &signer, &writable, &readonly, &program]);
+        let rest = &message[4 + 5 * 32..];
+        assert_eq!(rest[..32], blockhash);
+        // Одна инструкция: индекс программы, индексы аккаунтов в порядке инструкции, данные
+        assert_eq!(rest[32..], [1, 4, 3, 3, 2, 1, 2, 7, 8]);
+    }
+
+    #[test]
+    fn duplicate_accounts_merge_privileges() {
+        let (payer, account, program) = (key(1), key(2), key(3));
+        let instructions = [
+            Instruction { program_id: program, accounts: vec![AccountMeta { pubkey: account, is_signer: false, is_writable: false }], data: vec![] },
+            Instruction { program_id: program, accounts: vec![AccountMeta { pubkey: account, is_signer: false, is_writable: true }], data: vec![] },
+        ];
+        let message = compile_message(&payer, &instructions, &key(9));
+
+        assert_eq!(message[..4], [1, 0, 1, 3]);
+        assert_eq!(message[4 + 32..4 + 64], account);
+    }
+
+    #[test]
+    fn pack_splits_by_packet_size() {
+        let instruction = Instruction {
+            program_id: key(5),
+            accounts: vec![AccountMeta { pubkey: key(2), is_signer: false, is_writable: true }],
+            data: vec![0; 300],
+        };
+        let transactions = pack(&key(1), vec![instruction; 10], &key(9)).unwrap();
+
+        assert!(transactions.len() > 1);
+        assert!(transactions.iter().all(|transaction| transaction.serialize().len() <= PACKET_DATA_SIZE));
+
+        let oversized = Instruction { program_id: key(5), accounts: vec![], data: vec![0; PACKET_DATA_SIZE] };
+        assert!(pack(&key(1), vec![oversized], &key(9)).is_err());
+    }
+
+    #[test]
+    fn associated_token_address() {
+        let wallet = decode_pubkey("9iD1LM1wQ7zPhZ1LnYUyPBgvKW3d3TvDTBR3ZU4HHLhb").unwrap();
+        let token_program = decode_pubkey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
+        let mint = decode_pubkey("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap();
+        let program = decode_pubkey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL").unwrap();
+
+        let (address, bump) = find_program_address(&[&wallet, &token_program, &mint], &program).unwrap();
+        assert_eq!(encode_pubkey(&address), "A9iWTVvAV36y8ru35HaTtk1EqjknyQ6Kiqa8ScTKu6C2");
+        assert_eq!(bump, 254);
+        assert!(!address_audit::is_on_curve(&address));
+    }
+}
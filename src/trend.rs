@@ -0,0 +1,138 @@
+// src/trend.rs
+// Изменения балансов за стандартные окна (7/30/90 дней), восстановленные по истории транзакций
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::Utc;
+use futures::future::join_all;
+use serde_json::json;
+
+use crate::format;
+use crate::rpc;
+use crate::tx;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// Баланс кошелька сейчас и изменения баланса в транзакциях самого длинного окна
+struct WalletHistory {
+    current: u64,
+    // (время блока, баланс до, баланс после), от новых к старым
+    changes: Vec<(i64, u64, u64)>,
+}
+
+// Баланс в начале окна и изменения внутри него, от старых к новым
+struct Timeline {
+    start: u64,
+    deltas: Vec<(i64, i64)>,
+}
+
+// Минимум, максимум и средний по времени баланс за окно
+struct WindowStats {
+    start: u64,
+    end: u64,
+    min: u64,
+    max: u64,
+    average: u64,
+}
+
+// Отчет по каждому кошельку и по всем кошелькам вместе
+pub async fn trend_report(rpc_url: &str, wallets: &[String], windows: &[u32]) -> Result<()> {
+    let now = Utc::now().timestamp();
+    let longest = windows.iter().copied().max().unwrap_or(0);
+    let since = now - i64::from(longest) * SECONDS_PER_DAY;
+
+    let results = join_all(wallets.iter().map(|wallet| wallet_history(rpc_url, wallet, since, now))).await;
+
+    println!("Trend for {} wallets:", wallets.len());
+    let mut loaded = Vec::new();
+    for (wallet, result) in wallets.iter().zip(results) {
+        match result {
+            Ok(history) => {
+                println!("{}: {} SOL", wallet, format::sol(history.current));
+                for &days in windows {
+                    print_window(days, &history.timeline(now - i64::from(days) * SECONDS_PER_DAY), now);
+                }
+                loaded.push(history);
+            }
+            Err(e) => println!("Error fetching history for wallet {}: {:#}", wallet, e),
+        }
+    }
+
+    // Итог только по кошелькам, историю которых удалось загрузить
+    let current: u64 = loaded.iter().map(|history| history.current).sum();
+    println!("Total ({} wallets): {} SOL", loaded.len(), format::sol(current));
+    for &days in windows {
+        let from = now - i64::from(days) * SECONDS_PER_DAY;
+        let timelines: Vec<Timeline> = loaded.iter().map(|history| history.timeline(from)).collect();
+        let mut deltas: Vec<(i64, i64)> = timelines.iter().flat_map(|timeline| timeline.deltas.clone()).collect();
+        deltas.sort_by_key(|&(time, _)| time);
+        let total = Timeline { start: timelines.iter().map(|timeline| timeline.start).sum(), deltas };
+        print_window(days, &total, now);
+    }
+
+    Ok(())
+}
+
+fn print_window(days: u32, timeline: &Timeline, now: i64) {
+    let stats = timeline.stats(now - i64::from(days) * SECONDS_PER_DAY, now);
+    println!(
+        "  {}d: {} SOL (from {} SOL), min {} SOL, max {} SOL, avg {} SOL",
+        days,
+        format::signed_sol(stats.end as i64 - stats.start as i64),
+        format::sol(stats.start),
+        format::sol(stats.min),
+        format::sol(stats.max),
+        format::sol(stats.average)
+    );
+}
+
+// Текущий баланс и балансы до/после каждой транзакции начиная с момента since
+async fn wallet_history(rpc_url: &str, wallet: &str, since: i64, now: i64) -> Result<WalletHistory> {
+    let balance: rpc::WithContext<u64> = rpc::call_with_context(rpc_url, "getBalance", json!([wallet]))
+        .await
+        .with_context(|| format!("Failed to fetch balance for wallet: {}", wallet))?;
+
+    let signatures = tx::get_signatures_since(rpc_url, wallet, since).await?;
+    let mut changes = Vec::new();
+    for (info, transaction) in tx::get_transactions(rpc_url, signatures.iter().collect()).await {
+        let (pre, post) = tx::sol_balances(&transaction?, wallet)?;
+        // Транзакции без времени блока - самые свежие
+        changes.push((info.block_time.unwrap_or(now), pre, post));
+    }
+
+    Ok(WalletHistory { current: balance.value, changes })
+}
+
+impl WalletHistory {
+    // Баланс на момент from и изменения после него
+    fn timeline(&self, from: i64) -> Timeline {
+        let in_window: Vec<&(i64, u64, u64)> = self.changes.iter().filter(|(time, _, _)| *time >= from).collect();
+        // Без транзакций в окне баланс не менялся
+        let start = in_window.last().map_or(self.current, |&&(_, pre, _)| pre);
+        let deltas = in_window
+            .iter()
+            .rev()
+            .map(|&&(time, pre, post)| (time, post as i64 - pre as i64))
+            .collect();
+
+        Timeline { start, deltas }
+    }
+}
+
+impl Timeline {
+    fn stats(&self, from: i64, to: i64) -> WindowStats {
+        let (mut balance, mut min, mut max) = (self.start, self.start, self.start);
+        let (mut weighted, mut since) = (0u128, from);
+
+        for &(time, delta) in &self.deltas {
+            let time = time.clamp(from, to);
+            weighted += u128::from(balance) * (time - since) as u128;
+            since = time;
+            balance = balance.saturating_add_signed(delta);
+            min = min.min(balance);
+            max = max.max(balance);
+        }
+        weighted += u128::from(balance) * (to - since) as u128;
+
+        let average = if to > from { (weighted / (to - from) as u128) as u64 } else { balance };
+        WindowStats { start: self.start, end: balance, min, max, average }
+    }
+}
@@ -0,0 +1,119 @@
+// src/triage.rs
+// Сводка по произвольному списку адресов для разбора инцидентов: баланс, активность, владелец, токены, связи
+use anyhow::{Context as AnyhowContext, Result};
+use futures::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::account;
+use crate::flows;
+use crate::format;
+use crate::lookalike;
+use crate::token;
+use crate::transaction;
+use crate::tx;
+
+// Сколько адресов разбирается одновременно
+const CONCURRENT_ADDRESSES: usize = 8;
+
+// Строка сводки
+struct Row {
+    balance: u64,
+    last_activity: Option<i64>,
+    owner: String,
+    tokens: usize,
+    related: Vec<String>,
+}
+
+// Адреса из файла: первое поле каждой строки; пустые строки и комментарии # пропускаются
+fn read_addresses(file: &Path) -> Result<Vec<String>> {
+    let text = fs::read_to_string(file).with_context(|| format!("Failed to read address list: {:?}", file))?;
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split(|c: char| c == ',' || c.is_whitespace()).next())
+        .map(str::to_string)
+        .collect())
+}
+
+// Владелец аккаунта; известные программы - по имени
+fn owner_name(owner: &str) -> String {
+    match owner {
+        account::SYSTEM_PROGRAM => "system".to_string(),
+        account::TOKEN_PROGRAM => "token".to_string(),
+        account::TOKEN_2022_PROGRAM => "token-2022".to_string(),
+        account::STAKE_PROGRAM => "stake".to_string(),
+        _ => owner.to_string(),
+    }
+}
+
+// Сводка по одному адресу; связи ищутся в последних limit транзакциях
+async fn triage(rpc_url: &str, address: &str, wallets: &[String], limit: usize) -> Result<Row> {
+    transaction::decode_pubkey(address)?;
+    let info = account::get_account_info(rpc_url, address).await?;
+    let tokens = token::get_token_accounts(rpc_url, address).await?;
+    let signatures = tx::get_signatures(rpc_url, address, limit).await?;
+
+    let mut related = Vec::new();
+    if wallets.iter().any(|wallet| wallet == address) {
+        related.push("tracked".to_string());
+    }
+    if let Some(imitated) = wallets.iter().find(|wallet| lookalike::looks_alike(address, wallet)) {
+        related.push(format!("lookalike of {}", imitated));
+    }
+
+    // Отслеживаемые кошельки в общих транзакциях с числом таких транзакций
+    let mut shared: BTreeMap<&String, usize> = BTreeMap::new();
+    for (info, transaction) in tx::get_transactions(rpc_url, signatures.iter().collect()).await {
+        let keys = match transaction.and_then(|transaction| flows::account_keys(&transaction)) {
+            Ok(keys) => keys,
+            Err(e) => {
+                println!("Skipping transaction {}: {:#}", info.signature, e);
+                continue;
+            }
+        };
+        for wallet in wallets.iter().filter(|wallet| *wallet != address && keys.contains(wallet)) {
+            *shared.entry(wallet).or_default() += 1;
+        }
+    }
+    related.extend(shared.iter().map(|(wallet, count)| format!("{} txs with {}", count, wallet)));
+
+    Ok(Row {
+        balance: info.as_ref().map_or(0, |info| info.lamports),
+        last_activity: signatures.first().and_then(|info| info.block_time),
+        owner: info.as_ref().map_or_else(|| "not created".to_string(), |info| owner_name(&info.owner)),
+        tokens: tokens.iter().filter(|account| account.amount > 0).count(),
+        related,
+    })
+}
+
+// Таблица по всем адресам из файла; ошибка по одному адресу не останавливает разбор остальных
+pub async fn run(rpc_url: &str, file: &Path, wallets: &[String], limit: usize) -> Result<()> {
+    let addresses = read_addresses(file)?;
+    let rows: Vec<(&String, Result<Row>)> = stream::iter(&addresses)
+        .map(|address| async move { (address, triage(rpc_url, address, wallets, limit).await) })
+        .buffered(CONCURRENT_ADDRESSES)
+        .collect()
+        .await;
+
+    println!("Triage of {} addresses (relations from the last {} transactions of each):", addresses.len(), limit);
+    println!("{:<44} {:>16} {:<29} {:<12} {:>6}  related", "address", "balance SOL", "last activity", "owner", "tokens");
+    for (address, row) in rows {
+        match row {
+            Ok(row) => println!(
+                "{:<44} {:>16} {:<29} {:<12} {:>6}  {}",
+                address,
+                format::sol(row.balance),
+                row.last_activity.map_or_else(|| "never".to_string(), format::time),
+                row.owner,
+                row.tokens,
+                if row.related.is_empty() { "-".to_string() } else { row.related.join("; ") }
+            ),
+            Err(e) => println!("{:<44} ERROR {:#}", address, e),
+        }
+    }
+
+    Ok(())
+}
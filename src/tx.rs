@@ -0,0 +1,160 @@
+// src/tx.rs
+// Поиск транзакций по адресу и разбор изменений балансов в них
+use anyhow::{Context as AnyhowContext, Result};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::flows;
+use crate::rpc;
+
+// Запись из ответа getSignaturesForAddress
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<Value>,
+    // Текст memo в формате "[длина] текст", если он был в транзакции
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default, rename = "blockTime")]
+    pub block_time: Option<i64>,
+}
+
+impl SignatureInfo {
+    // Тексты всех memo транзакции без префикса длины.
+    // RPC отдает их одной строкой вида "[6] INV-42; [3] foo"
+    pub fn memos(&self) -> Vec<&str> {
+        let Some(field) = &self.memo else {
+            return Vec::new();
+        };
+
+        field
+            .split("; ")
+            .map(|part| match part.split_once("] ") {
+                Some((prefix, text)) if prefix.starts_with('[') => text,
+                _ => part,
+            })
+            .collect()
+    }
+}
+
+// Последние подписи транзакций, в которых участвовал адрес
+pub async fn get_signatures(rpc_url: &str, address: &str, limit: usize) -> Result<Vec<SignatureInfo>> {
+    rpc::call(
+        rpc_url,
+        "getSignaturesForAddress",
+        json!([address, { "limit": limit }]),
+    )
+    .await
+    .with_context(|| format!("Failed to fetch signatures for {}", address))
+}
+
+// Максимальный размер страницы getSignaturesForAddress
+const SIGNATURES_PAGE: usize = 1000;
+
+// Сколько транзакций загружается одновременно
+const CONCURRENT_FETCHES: usize = 16;
+
+// Все подписи адреса начиная с момента since (unix time), от новых к старым
+pub async fn get_signatures_since(rpc_url: &str, address: &str, since: i64) -> Result<Vec<SignatureInfo>> {
+    let mut all: Vec<SignatureInfo> = Vec::new();
+
+    loop {
+        let mut options = json!({ "limit": SIGNATURES_PAGE });
+        if let Some(last) = all.last() {
+            options["before"] = json!(last.signature);
+        }
+
+        let page: Vec<SignatureInfo> = rpc::call(rpc_url, "getSignaturesForAddress", json!([address, options]))
+            .await
+            .with_context(|| format!("Failed to fetch signatures for {}", address))?;
+        let page_len = page.len();
+
+        let mut reached_start = false;
+        for info in page {
+            // Транзакции без времени блока считаем попавшими в период
+            if info.block_time.is_some_and(|time| time < since) {
+                reached_start = true;
+                break;
+            }
+            all.push(info);
+        }
+
+        if reached_start || page_len < SIGNATURES_PAGE {
+            return Ok(all);
+        }
+    }
+}
+
+// Полная транзакция в json-кодировке
+pub async fn get_transaction(rpc_url: &str, signature: &str) -> Result<Value> {
+    rpc::call(
+        rpc_url,
+        "getTransaction",
+        json!([signature, { "encoding": "json", "maxSupportedTransactionVersion": 0 }]),
+    )
+    .await
+    .with_context(|| format!("Failed to fetch transaction {}", signature))
+}
+
+// Загрузка транзакций по списку подписей с ограничением параллелизма, порядок сохраняется
+pub async fn get_transactions<'a>(
+    rpc_url: &str,
+    signatures: Vec<&'a SignatureInfo>,
+) -> Vec<(&'a SignatureInfo, Result<Value>)> {
+    stream::iter(signatures)
+        .map(|info| async move { (info, get_transaction(rpc_url, &info.signature).await) })
+        .buffered(CONCURRENT_FETCHES)
+        .collect()
+        .await
+}
+
+// Полученная адресом сумма SOL в транзакции (без учета списаний)
+pub fn sol_received(transaction: &Value, recipient: &str) -> Result<u64> {
+    Ok(sol_delta(transaction, recipient)?.max(0) as u64)
+}
+
+// Изменение баланса SOL адреса в транзакции со знаком, включая комиссию
+pub fn sol_delta(transaction: &Value, address: &str) -> Result<i64> {
+    let (pre, post) = sol_balances(transaction, address)?;
+    Ok(post as i64 - pre as i64)
+}
+
+// Баланс SOL адреса до и после транзакции
+pub fn sol_balances(transaction: &Value, address: &str) -> Result<(u64, u64)> {
+    // Адреса из таблиц поиска (v0) идут в балансах после статических ключей
+    let index = flows::account_keys(transaction)?
+        .iter()
+        .position(|key| key == address)
+        .context("Address is not part of the transaction")?;
+
+    let balance_at = |field: &str| {
+        transaction.pointer(&format!("/meta/{}/{}", field, index))
+            .and_then(Value::as_u64)
+            .with_context(|| format!("Transaction meta has no {}", field))
+    };
+
+    Ok((balance_at("preBalances")?, balance_at("postBalances")?))
+}
+
+// Изменение баланса токена владельца и число знаков токена
+pub fn token_received(transaction: &Value, recipient: &str, mint: &str) -> Result<(u64, u32)> {
+    let find = |field: &str| -> Option<(u64, u32)> {
+        transaction.pointer(&format!("/meta/{}", field))?
+            .as_array()?
+            .iter()
+            .find(|b| b["owner"].as_str() == Some(recipient) && b["mint"].as_str() == Some(mint))
+            .and_then(|b| {
+                let amount = b.pointer("/uiTokenAmount/amount")?.as_str()?.parse().ok()?;
+                let decimals = b.pointer("/uiTokenAmount/decimals")?.as_u64()? as u32;
+                Some((amount, decimals))
+            })
+    };
+
+    let (post, decimals) = find("postTokenBalances")
+        .context("Recipient has no token balance for the requested mint in the transaction")?;
+    let pre = find("preTokenBalances").map_or(0, |(amount, _)| amount);
+
+    Ok((post.saturating_sub(pre), decimals))
+}
@@ -0,0 +1,182 @@
+// src/usage.rs
+// Учет RPC запросов и кредитов провайдера по эндпоинтам и дням с дневным лимитом
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+// Секция usage конфигурации
+#[derive(Debug, Deserialize)]
+pub struct UsageConfig {
+    // Файл с накопленными счетчиками
+    #[serde(default = "default_file")]
+    pub file: PathBuf,
+    // Стоимость вызова в кредитах провайдера по методу
+    #[serde(default)]
+    pub costs: HashMap<String, u64>,
+    #[serde(default = "default_cost")]
+    pub default_cost: u64,
+    // Лимит кредитов на эндпоинт в сутки (UTC); вызов сверх лимита не выполняется
+    pub daily_budget: Option<u64>,
+}
+
+fn default_file() -> PathBuf {
+    PathBuf::from("rpc-usage.json")
+}
+
+fn default_cost() -> u64 {
+    1
+}
+
+// Счетчики одного метода
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Counter {
+    pub requests: u64,
+    pub credits: u64,
+}
+
+// День (YYYY-MM-DD) -> эндпоинт -> метод -> счетчики
+pub type UsageLog = BTreeMap<String, BTreeMap<String, BTreeMap<String, Counter>>>;
+
+struct Tracker {
+    config: UsageConfig,
+    log: UsageLog,
+    // Счетчики текущего запуска: эндпоинт -> метод
+    run: BTreeMap<String, BTreeMap<String, Counter>>,
+}
+
+// Учет включается один раз при запуске
+static TRACKER: OnceLock<Mutex<Tracker>> = OnceLock::new();
+
+// Включение учета с загрузкой накопленных счетчиков
+pub fn enable(config: UsageConfig) -> Result<()> {
+    let log = load(&config.file)?;
+    let _ = TRACKER.set(Mutex::new(Tracker { config, log, run: BTreeMap::new() }));
+    Ok(())
+}
+
+// Накопленные счетчики из файла; отсутствующий файл - пустой журнал
+pub fn load(path: &Path) -> Result<UsageLog> {
+    if !path.exists() {
+        return Ok(UsageLog::new());
+    }
+    let text = fs::read_to_string(path).with_context(|| format!("Failed to read usage file: {:?}", path))?;
+    serde_json::from_str(&text).with_context(|| format!("Failed to parse usage file: {:?}", path))
+}
+
+// Эндпоинт без параметров запроса, в которых часто передается ключ API
+pub fn endpoint_key(rpc_url: &str) -> String {
+    rpc_url.split('?').next().unwrap_or_default().to_string()
+}
+
+fn today() -> String {
+    Utc::now().format("%Y-%m-%d").to_string()
+}
+
+// Учет вызова перед отправкой; ошибка, если вызов превысит дневной лимит эндпоинта
+pub fn charge(rpc_url: &str, method: &str) -> Result<()> {
+    let Some(tracker) = TRACKER.get() else {
+        return Ok(());
+    };
+    let mut tracker = tracker.lock().unwrap();
+    let tracker = &mut *tracker;
+
+    let endpoint = endpoint_key(rpc_url);
+    let cost = tracker.config.costs.get(method).copied().unwrap_or(tracker.config.default_cost);
+    let day = tracker.log.entry(today()).or_default().entry(endpoint.clone()).or_default();
+
+    if let Some(budget) = tracker.config.daily_budget {
+        let spent: u64 = day.values().map(|counter| counter.credits).sum();
+        if spent + cost > budget {
+            anyhow::bail!(
+                "Daily RPC budget for {} would be exceeded: {} of {} credits used, {} costs {}",
+                endpoint, spent, budget, method, cost
+            );
+        }
+    }
+
+    for counter in [day.entry(method.to_string()).or_default(), tracker.run.entry(endpoint).or_default().entry(method.to_string()).or_default()] {
+        counter.requests += 1;
+        counter.credits += cost;
+    }
+
+    Ok(())
+}
+
+// Итог запуска и сохранение счетчиков; вызывается в конце main
+pub fn finish() -> Result<()> {
+    let Some(tracker) = TRACKER.get() else {
+        return Ok(());
+    };
+    let tracker = tracker.lock().unwrap();
+
+    let today = today();
+    for (endpoint, methods) in &tracker.run {
+        let requests: u64 = methods.values().map(|counter| counter.requests).sum();
+        let credits: u64 = methods.values().map(|counter| counter.credits).sum();
+        let today_credits: u64 = tracker.log
+            .get(&today)
+            .and_then(|day| day.get(endpoint))
+            .map_or(0, |methods| methods.values().map(|counter| counter.credits).sum());
+        let budget = tracker.config.daily_budget.map_or_else(String::new, |budget| format!(" of {}", budget));
+        println!(
+            "RPC usage {}: {} requests, {} credits this run; {}{} credits today",
+            endpoint, requests, credits, today_credits, budget
+        );
+        print_methods(methods);
+    }
+
+    let text = serde_json::to_string_pretty(&tracker.log)?;
+    fs::write(&tracker.config.file, text)
+        .with_context(|| format!("Failed to write usage file: {:?}", tracker.config.file))
+}
+
+// Методы по убыванию потраченных кредитов
+fn print_methods(methods: &BTreeMap<String, Counter>) {
+    let mut sorted: Vec<(&String, &Counter)> = methods.iter().collect();
+    sorted.sort_by(|a, b| b.1.credits.cmp(&a.1.credits).then(a.0.cmp(b.0)));
+    for (method, counter) in sorted {
+        println!("  {}: {} requests, {} credits", method, counter.requests, counter.credits);
+    }
+}
+
+// Накопленное потребление по методам за последние days дней (все дни, если None)
+pub fn report(config: Option<&UsageConfig>, days: Option<u32>) -> Result<()> {
+    let path = config.map_or_else(default_file, |config| config.file.clone());
+    let log = load(&path)?;
+
+    let since = days.map(|days| (Utc::now() - chrono::Duration::days(i64::from(days) - 1)).format("%Y-%m-%d").to_string());
+    let selected: Vec<(&String, _)> = log
+        .iter()
+        .filter(|(day, _)| since.as_ref().is_none_or(|since| *day >= since))
+        .collect();
+    let (Some(first), Some(last)) = (selected.first(), selected.last()) else {
+        println!("No RPC usage recorded in {:?}", path);
+        return Ok(());
+    };
+
+    let mut by_endpoint: BTreeMap<&String, BTreeMap<String, Counter>> = BTreeMap::new();
+    for (_, endpoints) in &selected {
+        for (endpoint, methods) in *endpoints {
+            let totals = by_endpoint.entry(endpoint).or_default();
+            for (method, counter) in methods {
+                let total = totals.entry(method.clone()).or_default();
+                total.requests += counter.requests;
+                total.credits += counter.credits;
+            }
+        }
+    }
+
+    println!("RPC usage {} .. {}:", first.0, last.0);
+    for (endpoint, methods) in &by_endpoint {
+        let requests: u64 = methods.values().map(|counter| counter.requests).sum();
+        let credits: u64 = methods.values().map(|counter| counter.credits).sum();
+        println!("{}: {} requests, {} credits", endpoint, requests, credits);
+        print_methods(methods);
+    }
+
+    Ok(())
+}
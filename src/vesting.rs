@@ -0,0 +1,231 @@
+// src/vesting.rs
+// Контракты вестинга Streamflow и Bonfida, получателем которых является кошелек
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::Utc;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+use crate::account::{self, KeyedAccount};
+use crate::format;
+use crate::rpc;
+use crate::token;
+
+pub const STREAMFLOW_PROGRAM: &str = "strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUxfmKg5m";
+pub const BONFIDA_VESTING_PROGRAM: &str = "CChTq6PthWU82YZkbveA3WDf7s97BWhBK4Vx9bmsT743";
+
+// Смещения полей контракта Streamflow
+const STREAMFLOW_AMOUNT_WITHDRAWN: usize = 17;
+const STREAMFLOW_CANCELED_AT: usize = 25;
+const STREAMFLOW_END_TIME: usize = 33;
+const STREAMFLOW_RECIPIENT: usize = 113;
+const STREAMFLOW_MINT: usize = 177;
+const STREAMFLOW_DEPOSITED: usize = 417;
+const STREAMFLOW_PERIOD: usize = 425;
+const STREAMFLOW_AMOUNT_PER_PERIOD: usize = 433;
+const STREAMFLOW_CLIFF: usize = 441;
+const STREAMFLOW_CLIFF_AMOUNT: usize = 449;
+
+// Заголовок Bonfida: токен-аккаунт получателя, mint, флаг инициализации; затем графики по 16 байт
+const BONFIDA_MINT: usize = 32;
+const BONFIDA_HEADER_LEN: usize = 65;
+const BONFIDA_SCHEDULE_LEN: usize = 16;
+
+// Состояние контракта вестинга на текущий момент
+#[derive(Debug)]
+pub struct VestingContract {
+    pub address: String,
+    pub program: &'static str,
+    pub mint: String,
+    // Еще не разблокировано
+    pub locked: u64,
+    // Разблокировано, но не выведено из контракта
+    pub claimable: u64,
+    // Ближайшая разблокировка: время и сумма
+    pub next_unlock: Option<(i64, u64)>,
+    pub end_time: Option<i64>,
+}
+
+// Контракты вестинга кошелька в обеих программах; token_accounts - токен-аккаунты кошелька
+pub async fn get_vesting_contracts(
+    rpc_url: &str,
+    wallet: &str,
+    token_accounts: &[token::TokenAccount],
+    now: i64,
+) -> Result<Vec<VestingContract>> {
+    let mut contracts = Vec::new();
+
+    for keyed in program_accounts(rpc_url, STREAMFLOW_PROGRAM, STREAMFLOW_RECIPIENT, wallet).await? {
+        if let Some(contract) = parse_streamflow(&keyed, now)? {
+            contracts.push(contract);
+        }
+    }
+
+    // Получатель в Bonfida - токен-аккаунт, а не сам кошелек
+    for token_account in token_accounts {
+        for keyed in program_accounts(rpc_url, BONFIDA_VESTING_PROGRAM, 0, &token_account.address).await? {
+            if let Some(contract) = parse_bonfida(&keyed, now)? {
+                contracts.push(contract);
+            }
+        }
+    }
+
+    Ok(contracts)
+}
+
+// Аккаунты программы, у которых по смещению offset записан адрес address
+async fn program_accounts(rpc_url: &str, program: &str, offset: usize, address: &str) -> Result<Vec<KeyedAccount>> {
+    rpc::call(
+        rpc_url,
+        "getProgramAccounts",
+        json!([program, {
+            "encoding": "base64",
+            "filters": [{ "memcmp": { "offset": offset, "bytes": address } }]
+        }]),
+    )
+    .await
+    .with_context(|| format!("Failed to fetch accounts of program {} for {}", program, address))
+}
+
+// Данные аккаунта в кодировке base64
+fn account_data(keyed: &KeyedAccount) -> Result<Vec<u8>> {
+    account::raw_data(&keyed.account)
+        .with_context(|| format!("Account {} has invalid base64 data", keyed.pubkey))
+}
+
+// Поток Streamflow: cliff_amount в момент cliff, затем amount_per_period каждые period секунд
+fn parse_streamflow(keyed: &KeyedAccount, now: i64) -> Result<Option<VestingContract>> {
+    let data = account_data(keyed)?;
+    let field = |offset| account::read_u64(&data, offset)
+        .with_context(|| format!("Streamflow contract {} is too short", keyed.pubkey));
+
+    // Отмененный поток уже рассчитан с отправителем и получателем
+    if field(STREAMFLOW_CANCELED_AT)? != 0 {
+        return Ok(None);
+    }
+    let deposited = field(STREAMFLOW_DEPOSITED)?;
+    let withdrawn = field(STREAMFLOW_AMOUNT_WITHDRAWN)?;
+    let period = field(STREAMFLOW_PERIOD)?.max(1);
+    let per_period = field(STREAMFLOW_AMOUNT_PER_PERIOD)?;
+    let cliff = field(STREAMFLOW_CLIFF)? as i64;
+    let cliff_amount = field(STREAMFLOW_CLIFF_AMOUNT)?;
+
+    let (unlocked, next_unlock) = if now < cliff {
+        (0, Some((cliff, cliff_amount.min(deposited))))
+    } else {
+        let periods = (now - cliff) as u64 / period;
+        let unlocked = cliff_amount.saturating_add(periods.saturating_mul(per_period)).min(deposited);
+        let next = (unlocked < deposited)
+            .then(|| (cliff + ((periods + 1) * period) as i64, per_period.min(deposited - unlocked)));
+        (unlocked, next)
+    };
+
+    Ok(Some(VestingContract {
+        address: keyed.pubkey.clone(),
+        program: STREAMFLOW_PROGRAM,
+        mint: account::read_pubkey(&data, STREAMFLOW_MINT).context("Streamflow contract has no mint")?,
+        locked: deposited - unlocked,
+        claimable: unlocked.saturating_sub(withdrawn),
+        next_unlock,
+        end_time: Some(field(STREAMFLOW_END_TIME)? as i64),
+    }))
+}
+
+// Вестинг Bonfida: список (время разблокировки, сумма); выведенные графики обнуляются
+fn parse_bonfida(keyed: &KeyedAccount, now: i64) -> Result<Option<VestingContract>> {
+    let data = account_data(keyed)?;
+    if data.get(BONFIDA_HEADER_LEN - 1) != Some(&1) {
+        return Ok(None);
+    }
+
+    let schedules: Vec<(i64, u64)> = data[BONFIDA_HEADER_LEN..]
+        .chunks_exact(BONFIDA_SCHEDULE_LEN)
+        .filter_map(|chunk| Some((account::read_u64(chunk, 0)? as i64, account::read_u64(chunk, 8)?)))
+        .filter(|&(_, amount)| amount > 0)
+        .collect();
+    let pending = schedules.iter().filter(|&&(time, _)| time > now);
+
+    Ok(Some(VestingContract {
+        address: keyed.pubkey.clone(),
+        program: BONFIDA_VESTING_PROGRAM,
+        mint: account::read_pubkey(&data, BONFIDA_MINT).context("Vesting account has no mint")?,
+        locked: pending.clone().map(|&(_, amount)| amount).sum(),
+        claimable: schedules.iter().filter(|&&(time, _)| time <= now).map(|&(_, amount)| amount).sum(),
+        next_unlock: pending.clone().min_by_key(|&&(time, _)| time).copied(),
+        end_time: pending.map(|&(time, _)| time).max(),
+    }))
+}
+
+// Отчет по всем кошелькам: свободные и заблокированные суммы по каждому токену
+pub async fn run_report(rpc_url: &str, wallets: &[String]) -> Result<()> {
+    let now = Utc::now().timestamp();
+
+    for wallet in wallets {
+        println!("Vesting for {}:", wallet);
+        if let Err(e) = report_wallet(rpc_url, wallet, now).await {
+            println!("  Error: {:#}", e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn report_wallet(rpc_url: &str, wallet: &str, now: i64) -> Result<()> {
+    let token_accounts = token::get_token_accounts(rpc_url, wallet).await?;
+    let contracts = get_vesting_contracts(rpc_url, wallet, &token_accounts, now).await?;
+    if contracts.is_empty() {
+        println!("  no vesting contracts");
+        return Ok(());
+    }
+
+    // Свободный остаток - токены на аккаунтах кошелька
+    let mut totals: BTreeMap<String, (u64, u64, u64)> = BTreeMap::new();
+    let mut decimals: BTreeMap<String, u32> = BTreeMap::new();
+    for account in token_accounts {
+        decimals.insert(account.mint.clone(), account.decimals);
+        totals.entry(account.mint).or_default().0 += account.amount;
+    }
+    for contract in &contracts {
+        if !decimals.contains_key(&contract.mint) {
+            decimals.insert(contract.mint.clone(), token::mint_decimals(rpc_url, &contract.mint).await?);
+        }
+    }
+
+    for contract in &contracts {
+        let shown = decimals[&contract.mint];
+        let entry = totals.entry(contract.mint.clone()).or_default();
+        entry.1 += contract.claimable;
+        entry.2 += contract.locked;
+
+        let program = if contract.program == STREAMFLOW_PROGRAM { "Streamflow" } else { "Bonfida" };
+        println!(
+            "  {} {} ({}): locked {}, claimable {}",
+            program,
+            contract.address,
+            contract.mint,
+            format::units(contract.locked, shown),
+            format::units(contract.claimable, shown)
+        );
+        if let Some((time, amount)) = contract.next_unlock {
+            println!("    next unlock {} at {}", format::units(amount, shown), format::time(time));
+        }
+        if let Some(end) = contract.end_time.filter(|&end| end > now) {
+            println!("    fully unlocked at {}", format::time(end));
+        }
+    }
+
+    for (mint, (liquid, claimable, locked)) in &totals {
+        if *claimable == 0 && *locked == 0 {
+            continue;
+        }
+        let shown = decimals[mint];
+        println!(
+            "  {}: liquid {}, claimable {}, locked {}",
+            mint,
+            format::units(*liquid, shown),
+            format::units(*claimable, shown),
+            format::units(*locked, shown)
+        );
+    }
+
+    Ok(())
+}